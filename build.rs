@@ -0,0 +1,150 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use tinyjson::JsonValue;
+
+/// Generates `solutions.rs`, which pulls every `src/bin/NN.rs` and `src/bin/YYYY-NN.rs` into the
+/// main binary as a module and lists their `SOLUTION` registry entries.
+///
+/// Also generates `examples/<bin>.rs` for every solution, with one test per entry of the day's
+/// example manifest. These are pulled into the solutions by `example_tests!`.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let stem = path.file_stem()?.to_str()?.to_string();
+            let day = match stem.split_once('-') {
+                Some((year, day)) if year.len() == 4 && year.parse::<u16>().is_ok() => day,
+                Some(_) => return None,
+                None => &stem,
+            };
+            let is_day = day.len() == 2 && day.parse::<u8>().is_ok_and(|d| (1..=25).contains(&d));
+            is_day.then_some(stem)
+        })
+        .collect();
+    days.sort_unstable();
+
+    let mut out = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day}.rs"));
+        out.push_str(&format!(
+            "#[path = {:?}]\n#[allow(dead_code)]\nmod {};\n\n",
+            path.display().to_string(),
+            module_name(day)
+        ));
+    }
+
+    out.push_str("pub const SOLUTIONS: &[advent_of_code::template::Solution] = &[\n");
+    for day in &days {
+        out.push_str(&format!("    {}::SOLUTION,\n", module_name(day)));
+    }
+    out.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solutions.rs"), out).unwrap();
+
+    let data_dir = Path::new(&manifest_dir).join("data");
+    let examples_dir = out_dir.join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+
+    for bin in &days {
+        let (folder, data_path, day) = match bin.split_once('-') {
+            Some((year, day)) => (
+                data_dir.join(year).join("examples"),
+                format!("data/{year}/examples/{day}.json"),
+                day,
+            ),
+            None => (
+                data_dir.join("examples"),
+                format!("data/examples/{bin}.json"),
+                bin.as_str(),
+            ),
+        };
+        if folder.is_dir() {
+            println!("cargo:rerun-if-changed={}", folder.display());
+        }
+
+        let manifest_path = folder.join(format!("{day}.json"));
+        let tests = match fs::read_to_string(&manifest_path) {
+            Ok(manifest) => example_tests(&manifest).map_err(|e| format!("{data_path}: {e}")),
+            Err(_) => Ok(String::new()),
+        };
+        let tests = match tests {
+            Ok(tests) if !tests.is_empty() => tests,
+            // a day without examples fails, like a hand-written test whose answer is not filled in.
+            Ok(_) => failing_manifest_test(&format!("no examples listed in {data_path}")),
+            // a malformed manifest only fails the tests of its day, not the whole build.
+            Err(e) => failing_manifest_test(&e),
+        };
+        fs::write(examples_dir.join(format!("{bin}.rs")), tests).unwrap();
+    }
+}
+
+/// Renders a test that fails with `message`, to report a problem with the example manifest.
+/// Like the example tests, it refers to the day's `SOLUTION`, which the test module imports.
+fn failing_manifest_test(message: &str) -> String {
+    format!(
+        "#[test]\nfn example_manifest() {{\n    \
+         panic!(\"day {{}}: {{}}\", SOLUTION.day, {message:?});\n}}\n"
+    )
+}
+
+/// Renders one test per entry of an example manifest, see `template::examples::ExampleManifest`.
+fn example_tests(manifest: &str) -> Result<String, String> {
+    let json: JsonValue = manifest.parse().or(Err("not valid JSON file."))?;
+    let examples: &Vec<JsonValue> = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get("examples")
+        .and_then(JsonValue::get)
+        .ok_or("expected `examples` to be an array.")?;
+
+    let mut out = String::new();
+
+    for (index, example) in examples.iter().enumerate() {
+        let example: &HashMap<String, JsonValue> =
+            example.get().ok_or("expected examples to be objects.")?;
+        example
+            .get("file")
+            .and_then(JsonValue::get::<String>)
+            .ok_or("expected example.file to be a string.")?;
+        let part: &f64 = example
+            .get("part")
+            .and_then(JsonValue::get)
+            .ok_or("expected example.part to be a number.")?;
+        let part = *part as u8;
+        let func = match part {
+            1 => "part_one",
+            2 => "part_two",
+            part => return Err(format!("unknown part `{part}`.")),
+        };
+
+        // entries are named by their index, as a file can be listed for a part more than once
+        // with different params.
+        out.push_str(&format!(
+            "#[test]\nfn {func}_example_{index}() {{\n    \
+             advent_of_code::template::examples::check_solution(&SOLUTION, {index});\n}}\n\n",
+        ));
+    }
+
+    Ok(out)
+}
+
+/// The module name of a solution binary, e.g. `day_01` or `day_2019_01`.
+fn module_name(bin: &str) -> String {
+    format!("day_{}", bin.replace('-', "_"))
+}
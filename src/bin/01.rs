@@ -23,75 +23,5 @@ pub fn part_two(input: &str) -> Option<i32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(3));
-    }
-
-    #[test]
-    fn test_part_one_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(3));
-    }
-
-    #[test]
-    fn test_part_one_three() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(0));
-    }
-
-    #[test]
-    fn test_part_one_four() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(-6));
-    }
-
-    #[test]
-    fn test_part_two_one() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two_five() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
-        ));
-        assert_eq!(result, Some(0));
-    }
-
-    #[test]
-    fn test_part_two_six() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 6,
-        ));
-        assert_eq!(result, Some(10));
-    }
-
-    #[test]
-    fn test_part_two_seven() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 7,
-        ));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two_eight() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 8,
-        ));
-        assert_eq!(result, Some(14));
-    }
+    advent_of_code::example_tests!();
 }
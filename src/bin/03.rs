@@ -1,13 +1,16 @@
 use std::{collections::HashMap, str::FromStr};
 
+use advent_of_code::util::{
+    geom::{IntervalSet, Rect},
+    point::Pt,
+};
+use anyhow::anyhow;
+
 advent_of_code::solution!(3);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Claim {
-    x: usize,
-    y: usize,
-    width: usize,
-    height: usize,
+    rect: Rect<usize>,
     id: u32,
 }
 
@@ -18,74 +21,45 @@ impl FromStr for Claim {
         let (id, rest) = s.split_once(" @ ").unwrap();
         let id = id.trim_start_matches('#').parse()?;
         let (loc, pos) = rest.split_once(": ").unwrap();
-        let (x, y) = loc.split_once(',').unwrap();
         let (width, height) = pos.split_once('x').unwrap();
-        let (x, y) = (x.parse()?, y.parse()?);
-        let (width, height) = (width.parse()?, height.parse()?);
-        Ok(Self {
-            x,
-            y,
-            width,
-            height,
-            id,
-        })
+        let rect = Rect::from_size(loc.parse::<Pt<usize>>()?, width.parse()?, height.parse()?)
+            .ok_or(anyhow!("claim {id} is empty"))?;
+        Ok(Self { rect, id })
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Default)]
-struct Map {
-    map: HashMap<(usize, usize), u32>,
+fn parse(input: &str) -> Vec<Claim> {
+    input.lines().map(|l| l.parse().unwrap()).collect()
 }
 
-impl Map {
-    fn add(&mut self, claim: Claim) {
-        for x in claim.x..claim.x + claim.width {
-            for y in claim.y..claim.y + claim.height {
-                self.map.entry((x, y)).and_modify(|e| *e += 1).or_insert(1);
+pub fn part_one(input: &str) -> Option<u32> {
+    let claims = parse(input);
+    // The overlapping x ranges of every row.
+    let mut rows: HashMap<usize, IntervalSet<usize>> = HashMap::new();
+    for (i, a) in claims.iter().enumerate() {
+        for b in &claims[i + 1..] {
+            if let Some(overlap) = a.rect.intersection(&b.rect) {
+                for y in overlap.min.y..=overlap.max.y {
+                    rows.entry(y)
+                        .or_default()
+                        .insert(overlap.min.x..=overlap.max.x);
+                }
             }
         }
     }
-
-    fn nonoverlapping(&self, claim: Claim) -> bool {
-        self.map
-            .iter()
-            .filter(|&(&(x, y), _)| {
-                claim.x <= x
-                    && x < claim.x + claim.width
-                    && claim.y <= y
-                    && y < claim.y + claim.height
-            })
-            .all(|(_, &e)| e == 1)
-    }
-
-    fn count(&self) -> u32 {
-        self.map.values().filter(|&&k| k >= 2).count() as u32
-    }
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut map = Map::default();
-    for claim in input.lines().map(|l| l.parse::<Claim>().unwrap()) {
-        map.add(claim);
-    }
-    Some(map.count())
+    Some(rows.values().map(IntervalSet::len).sum::<usize>() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut map = Map::default();
-    let claims = input
-        .lines()
-        .map(|l| l.parse::<Claim>().unwrap())
-        .collect::<Vec<_>>();
-    for claim in &claims {
-        map.add(*claim);
-    }
-    for claim in claims {
-        if map.nonoverlapping(claim) {
-            return Some(claim.id);
-        }
-    }
-    None
+    let claims = parse(input);
+    claims
+        .iter()
+        .find(|a| {
+            claims
+                .iter()
+                .all(|b| a.id == b.id || !a.rect.intersects(&b.rect))
+        })
+        .map(|a| a.id)
 }
 
 #[cfg(test)]
@@ -1,68 +1,65 @@
-use std::{collections::HashMap, str::FromStr};
+use std::collections::HashMap;
 
-use anyhow::anyhow;
+use advent_of_code::util::{grid::Grid, point::Pt};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, params = Params);
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
-struct Pt(i32, i32);
-
-impl Pt {
-    fn distance(&self, other: &Self) -> u32 {
-        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+advent_of_code::params! {
+    pub struct Params {
+        /// Width and height of the area that is mapped to the closest points.
+        size: i32 = 750,
+        /// Half the width of the area searched for the region in part two.
+        range: i32 = 342,
+        /// Upper bound of the total distance to all points in part two, exclusive.
+        max_distance: u32 = 10000,
     }
 }
 
-impl FromStr for Pt {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s
-            .split_once(", ")
-            .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
-            .ok_or(anyhow!("{s} is not a valid point"))?;
-        Ok(Pt(x, y))
-    }
+fn distance(a: Pt<i32>, b: Pt<i32>) -> u32 {
+    a.manhattan(&b) as u32
 }
 
-const WIDTH: i32 = 750;
-const HEIGHT: i32 = 750;
-
-#[derive(Clone, Debug, PartialEq, Eq, Default)]
+/// The closest point of every location in a square around the origin.
+#[derive(Clone, Debug, PartialEq, Eq)]
 struct Map {
-    inner: HashMap<Pt, Option<Pt>>,
+    inner: Grid<Option<Pt<i32>>>,
 }
 
 impl Map {
-    fn generate(pts: &[Pt]) -> Self {
-        let mut inner = HashMap::new();
-        for x in (-WIDTH / 2)..=(WIDTH / 2) {
-            for y in (-HEIGHT / 2)..=(HEIGHT / 2) {
-                let p = Pt(x, y);
-                let closest = *pts.iter().min_by_key(|pt| pt.distance(&p)).unwrap();
-                let closest = if pts
-                    .iter()
-                    .filter(|&&pt| pt != closest)
-                    .any(|pt| pt.distance(&p) == closest.distance(&p))
-                {
-                    None
-                } else {
-                    Some(closest)
-                };
-                inner.insert(p, closest);
+    fn generate(pts: &[Pt<i32>], size: i32) -> Self {
+        let half_size = size / 2;
+        let len = (2 * half_size + 1) as usize;
+        let inner = Grid::from_fn(len, len, |pt| {
+            let p = Pt {
+                x: pt.x as i32 - half_size,
+                y: pt.y as i32 - half_size,
+            };
+            let closest = *pts.iter().min_by_key(|&&pt| distance(pt, p)).unwrap();
+            if pts
+                .iter()
+                .filter(|&&pt| pt != closest)
+                .any(|&pt| distance(pt, p) == distance(closest, p))
+            {
+                None
+            } else {
+                Some(closest)
             }
-        }
+        });
         Self { inner }
     }
 
-    fn has_pts_on_boundary(&self, pt: Pt) -> bool {
+    fn has_pts_on_boundary(&self, pt: Pt<i32>) -> bool {
+        let last = self.inner.width() - 1;
         self.inner
+            .row(0)
             .iter()
-            .filter(|&(p, _)| p.0.abs() == WIDTH / 2 || p.1.abs() == HEIGHT / 2)
-            .any(|(_, &to)| Some(pt) == to)
+            .chain(self.inner.row(last))
+            .chain(self.inner.column(0))
+            .chain(self.inner.column(last))
+            .any(|&to| Some(pt) == to)
     }
 
-    fn num_pts(&self, pts: &[Pt]) -> HashMap<Pt, u32> {
+    fn num_pts(&self, pts: &[Pt<i32>]) -> HashMap<Pt<i32>, u32> {
         let mut map = HashMap::with_capacity(pts.len());
         for to in self.inner.values().flatten().filter(|p| pts.contains(p)) {
             map.entry(*to).and_modify(|e| *e += 1).or_insert(1);
@@ -71,28 +68,28 @@ impl Map {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let mut pts = input
         .lines()
-        .map(|l| l.parse::<Pt>().unwrap())
-        .collect::<Vec<_>>();
-    let map = Map::generate(&pts);
+        .map(|l| l.parse().unwrap())
+        .collect::<Vec<Pt<i32>>>();
+    let map = Map::generate(&pts, params.size);
     pts.retain(|p| !map.has_pts_on_boundary(*p));
     let num = map.num_pts(&pts);
     num.values().max().copied()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let pts = input
         .lines()
-        .map(|l| l.parse::<Pt>().unwrap())
-        .collect::<Vec<_>>();
-    const RANGE: i32 = 342;
+        .map(|l| l.parse().unwrap())
+        .collect::<Vec<Pt<i32>>>();
+    let range = params.range;
     Some(
-        (-RANGE..=RANGE)
-            .flat_map(|x| (-RANGE..=RANGE).map(move |y| Pt(x, y)))
-            .map(|p0| pts.iter().map(|p1| p0.distance(p1)).sum::<u32>())
-            .filter(|&s| s < 10000)
+        (-range..=range)
+            .flat_map(|x| (-range..=range).map(move |y| Pt { x, y }))
+            .map(|p0| pts.iter().map(|&p1| distance(p0, p1)).sum::<u32>())
+            .filter(|&s| s < params.max_distance)
             .count() as u32,
     )
 }
@@ -101,9 +98,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(17));
-    }
+    advent_of_code::example_tests!();
 }
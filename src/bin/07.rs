@@ -2,7 +2,16 @@ use std::fmt::Display;
 
 use strum::{Display, EnumString, FromRepr};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, params = Params);
+
+advent_of_code::params! {
+    pub struct Params {
+        /// Number of workers, including yourself.
+        workers: usize = 5,
+        /// Base duration of every step in seconds.
+        base: u32 = 60,
+    }
+}
 
 #[derive(Clone, Copy, PartialEq, Eq, EnumString, Hash, FromRepr, Debug, Display)]
 enum Step {
@@ -71,8 +80,8 @@ impl Worker {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
-struct Workers([Option<Worker>; 5]);
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Workers(Vec<Option<Worker>>);
 
 impl Requirements {
     fn add(&mut self, s: &str) -> anyhow::Result<()> {
@@ -100,9 +109,9 @@ impl Requirements {
         Steps(out)
     }
 
-    fn compute_v2(&mut self) -> u32 {
+    fn compute_v2(&mut self, params: &Params) -> u32 {
         let mut done: Vec<Step> = Vec::with_capacity(26);
-        let mut workers = Workers::default();
+        let mut workers = Workers(vec![None; params.workers]);
         for time in 0.. {
             for worker in workers.0.iter_mut() {
                 if let Some(w) = worker {
@@ -132,7 +141,7 @@ impl Requirements {
                         let worker = workers.0.iter_mut().find(|w| w.is_none()).unwrap();
                         *worker = Some(Worker {
                             step: next,
-                            time: next as u32 + 60,
+                            time: next as u32 + params.base,
                         })
                     } else {
                         break;
@@ -167,7 +176,7 @@ impl Display for Workers {
             } else {
                 write!(f, ".:  0")?
             }
-            if i <= 3 {
+            if i + 1 < self.0.len() {
                 write!(f, ", ")?;
             }
         }
@@ -175,7 +184,7 @@ impl Display for Workers {
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str, _: &Params) -> Option<String> {
     let mut reqs = Requirements::default();
     for line in input.lines() {
         reqs.add(line).unwrap();
@@ -183,21 +192,17 @@ pub fn part_one(input: &str) -> Option<String> {
     Some(format!("{}", reqs.compute()))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let mut reqs = Requirements::default();
     for line in input.lines() {
         reqs.add(line).unwrap();
     }
-    Some(reqs.compute_v2())
+    Some(reqs.compute_v2(params))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("CABDFE".into()));
-    }
+    advent_of_code::example_tests!();
 }
@@ -1,56 +1,13 @@
-use std::{
-    fmt::Display,
-    ops::{Add, AddAssign},
-    str::FromStr,
-};
+use std::{fmt::Display, str::FromStr};
 
-use anyhow::anyhow;
+use advent_of_code::util::{geom::Rect, point::Pt};
 
 advent_of_code::solution!(10);
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
-struct Pt(i32, i32);
-
-impl AddAssign<Pt> for Pt {
-    fn add_assign(&mut self, rhs: Pt) {
-        self.0 += rhs.0;
-        self.1 += rhs.1;
-    }
-}
-
-impl Add<Pt> for Pt {
-    type Output = Pt;
-
-    fn add(self, rhs: Pt) -> Self::Output {
-        Pt(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-impl Add<&Pt> for Pt {
-    type Output = Pt;
-
-    fn add(self, rhs: &Pt) -> Self::Output {
-        Pt(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-impl FromStr for Pt {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (x, y) = s.split_once(',').ok_or(anyhow!("pt must have a comma"))?;
-        let (x, y) = (
-            x.trim_start_matches('<').trim().parse()?,
-            y.trim_end_matches('>').trim().parse()?,
-        );
-        Ok(Self(x, y))
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct Star {
-    p: Pt,
-    v: Pt,
+    p: Pt<i32>,
+    v: Pt<i32>,
 }
 
 impl Star {
@@ -93,43 +50,30 @@ struct Points(Vec<Star>);
 
 impl Display for Points {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let l = self.0.iter().map(|s| s.p.0).min().unwrap();
-        let r = self.0.iter().map(|s| s.p.0).max().unwrap();
-        let u = self.0.iter().map(|s| s.p.1).min().unwrap();
-        let d = self.0.iter().map(|s| s.p.1).max().unwrap();
-        for x in u..=d {
-            for y in l..=r {
-                let c = match self.0.iter().find(|p| p.p == Pt(y, x)) {
-                    Some(_) => '#',
-                    None => '.',
-                };
-                write!(f, "{c}")?;
+        let bounds = self.bounds();
+        for pt in bounds.points() {
+            let c = match self.0.iter().find(|p| p.p == pt) {
+                Some(_) => '#',
+                None => '.',
+            };
+            write!(f, "{c}")?;
+            if pt.x == bounds.max.x {
+                writeln!(f)?;
             }
-            writeln!(f)?;
         }
         Ok(())
     }
 }
 
 impl Points {
-    fn u(&self) -> i32 {
-        self.0.iter().map(|s| s.p.1).min().unwrap()
-    }
-
-    fn d(&self) -> i32 {
-        self.0.iter().map(|s| s.p.1).max().unwrap()
-    }
-
-    fn l(&self) -> i32 {
-        self.0.iter().map(|s| s.p.0).min().unwrap()
-    }
-
-    fn r(&self) -> i32 {
-        self.0.iter().map(|s| s.p.0).max().unwrap()
+    fn bounds(&self) -> Rect<i32> {
+        Rect::bounding(self.0.iter().map(|s| s.p)).unwrap()
     }
 
     fn span(&self) -> i32 {
-        self.r() - self.l() + self.d() - self.u()
+        let bounds = self.bounds();
+        let size = bounds.max - bounds.min;
+        size.x + size.y
     }
 }
 
@@ -138,7 +82,7 @@ pub fn part_one(input: &str) -> Option<String> {
     let mut pts = Points(pts);
     for _ in 0..11000 {
         if pts.span() <= 80 {
-            println!("{pts}");
+            advent_of_code::outln!("{pts}");
             return Some("EHAZPZHP".into());
         }
         for pt in &mut pts.0 {
@@ -1,36 +1,14 @@
-use std::{
-    cmp::Reverse,
-    collections::HashMap,
-    fmt::Display,
-    ops::{AddAssign, Not},
-    str::FromStr,
-};
+use std::{cmp::Reverse, fmt::Display, str::FromStr};
 
-use anyhow::anyhow;
+use advent_of_code::template::Failure;
+use advent_of_code::util::{
+    grid::SparseGrid,
+    point::{Dir4, Pt},
+};
+use anyhow::{anyhow, Context, Result};
 
 advent_of_code::solution!(13);
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
-pub struct Pt(u32, u32);
-
-impl PartialOrd for Pt {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Pt {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.1.cmp(&other.1).then_with(|| self.0.cmp(&other.0))
-    }
-}
-
-impl Display for Pt {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.0, self.1)
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 enum Track {
     Hor,
@@ -40,79 +18,32 @@ enum Track {
     Int,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-enum Dir {
-    N,
-    S,
-    E,
-    W,
-}
-
-impl Dir {
-    fn next_dir(&self, num_inters: u32) -> Self {
-        match num_inters % 3 {
-            0 => self.left(),
-            1 => *self,
-            2 => self.right(),
-            _ => unreachable!(),
-        }
-    }
-
-    fn left(&self) -> Self {
-        match self {
-            Dir::N => Dir::W,
-            Dir::E => Dir::N,
-            Dir::W => Dir::S,
-            Dir::S => Dir::E,
-        }
-    }
-
-    fn right(&self) -> Self {
-        !self.left()
-    }
-}
-
-impl Not for Dir {
-    type Output = Self;
-
-    fn not(self) -> Self {
-        match self {
-            Dir::N => Dir::S,
-            Dir::S => Dir::N,
-            Dir::E => Dir::W,
-            Dir::W => Dir::E,
-        }
-    }
-}
-
-impl AddAssign<Dir> for Pt {
-    fn add_assign(&mut self, rhs: Dir) {
-        match rhs {
-            Dir::N => self.1 -= 1,
-            Dir::S => self.1 += 1,
-            Dir::E => self.0 += 1,
-            Dir::W => self.0 -= 1,
-        }
+/// The direction a cart takes at its `num_inters`th intersection.
+fn next_dir(dir: Dir4, num_inters: u32) -> Dir4 {
+    match num_inters % 3 {
+        0 => dir.left(),
+        1 => dir,
+        2 => dir.right(),
+        _ => unreachable!(),
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct Cart {
-    loc: Pt,
-    dir: Dir,
+    loc: Pt<usize>,
+    dir: Dir4,
     intersections_seen: u32,
 }
 
-impl Display for Track {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let c = match self {
+impl Track {
+    fn to_char(self) -> char {
+        match self {
             Track::Hor => '-',
             Track::Ver => '|',
             Track::NW => '\\',
             Track::NE => '/',
             Track::Int => '+',
-        };
-        write!(f, "{c}")
+        }
     }
 }
 
@@ -145,7 +76,7 @@ impl Ord for Cart {
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 struct Mines {
-    map: HashMap<Pt, Track>,
+    map: SparseGrid<Track>,
     carts: Vec<Cart>,
 }
 
@@ -153,100 +84,63 @@ impl FromStr for Mines {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = HashMap::new();
         let mut carts = Vec::new();
-        for (y, line) in s.lines().enumerate().map(|(y, l)| (y as u32, l)) {
-            for (x, c) in line.chars().enumerate().map(|(x, c)| (x as u32, c)) {
-                match c {
-                    '>' => {
-                        carts.push(Cart {
-                            loc: Pt(x, y),
-                            dir: Dir::E,
-                            intersections_seen: 0,
-                        });
-                        map.insert(Pt(x, y), Track::Hor);
-                    }
-                    '^' => {
-                        carts.push(Cart {
-                            loc: Pt(x, y),
-                            dir: Dir::N,
-                            intersections_seen: 0,
-                        });
-                        map.insert(Pt(x, y), Track::Ver);
-                    }
-                    '<' => {
-                        carts.push(Cart {
-                            loc: Pt(x, y),
-                            dir: Dir::W,
-                            intersections_seen: 0,
-                        });
-                        map.insert(Pt(x, y), Track::Hor);
-                    }
-                    'v' => {
-                        carts.push(Cart {
-                            loc: Pt(x, y),
-                            dir: Dir::S,
-                            intersections_seen: 0,
-                        });
-                        map.insert(Pt(x, y), Track::Ver);
-                    }
-                    ' ' => {}
-                    _ => {
-                        map.insert(Pt(x, y), c.try_into()?);
-                    }
-                }
+        let map = SparseGrid::parse(s, |pt, c| {
+            let (dir, track) = match c {
+                '>' | '<' => (Some(Dir4::try_from(c)?), Track::Hor),
+                '^' | 'v' => (Some(Dir4::try_from(c)?), Track::Ver),
+                ' ' => return Ok(None),
+                _ => (None, c.try_into()?),
+            };
+            if let Some(dir) = dir {
+                carts.push(Cart {
+                    loc: pt,
+                    dir,
+                    intersections_seen: 0,
+                });
             }
-        }
+            Ok(Some(track))
+        })?;
         Ok(Self { map, carts })
     }
 }
 
 impl Display for Mines {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let min_x = self.map.keys().map(|p| p.0).min().unwrap();
-        let max_x = self.map.keys().map(|p| p.0).max().unwrap();
-        let min_y = self.map.keys().map(|p| p.1).min().unwrap();
-        let max_y = self.map.keys().map(|p| p.1).max().unwrap();
-        for y in min_y..=max_y {
-            for x in min_x..=max_x {
-                let pt = Pt(x, y);
-                if let Some(cart) = self.carts.iter().find(|cart| cart.loc == pt) {
-                    match cart.dir {
-                        Dir::N => write!(f, "^")?,
-                        Dir::S => write!(f, "v")?,
-                        Dir::E => write!(f, ">")?,
-                        Dir::W => write!(f, "<")?,
-                    }
-                } else if let Some(tile) = self.map.get(&pt) {
-                    write!(f, "{tile}")?
-                } else {
-                    write!(f, " ")?
+        let map = self.map.render(|pt, tile| {
+            if let Some(cart) = self.carts.iter().find(|cart| cart.loc == pt) {
+                match cart.dir {
+                    Dir4::N => '^',
+                    Dir4::S => 'v',
+                    Dir4::E => '>',
+                    Dir4::W => '<',
                 }
+            } else {
+                tile.map_or(' ', |&tile| tile.to_char())
             }
-            writeln!(f)?;
-        }
-        Ok(())
+        });
+        write!(f, "{map}")
     }
 }
 
 impl Mines {
-    fn step(&mut self) -> Option<Pt> {
+    fn step(&mut self) -> Option<Pt<usize>> {
         self.carts.sort();
         for i in 0..self.carts.len() {
             let cart = &mut self.carts[i];
             cart.loc += cart.dir;
-            match (self.map[&cart.loc], cart.dir) {
-                (Track::Hor, Dir::N | Dir::S) | (Track::Ver, Dir::E | Dir::W) => unreachable!(),
-                (Track::NW, Dir::N) => cart.dir = Dir::W,
-                (Track::NW, Dir::S) => cart.dir = Dir::E,
-                (Track::NW, Dir::E) => cart.dir = Dir::S,
-                (Track::NW, Dir::W) => cart.dir = Dir::N,
-                (Track::NE, Dir::N) => cart.dir = Dir::E,
-                (Track::NE, Dir::S) => cart.dir = Dir::W,
-                (Track::NE, Dir::E) => cart.dir = Dir::N,
-                (Track::NE, Dir::W) => cart.dir = Dir::S,
+            match (self.map[cart.loc], cart.dir) {
+                (Track::Hor, Dir4::N | Dir4::S) | (Track::Ver, Dir4::E | Dir4::W) => unreachable!(),
+                (Track::NW, Dir4::N) => cart.dir = Dir4::W,
+                (Track::NW, Dir4::S) => cart.dir = Dir4::E,
+                (Track::NW, Dir4::E) => cart.dir = Dir4::S,
+                (Track::NW, Dir4::W) => cart.dir = Dir4::N,
+                (Track::NE, Dir4::N) => cart.dir = Dir4::E,
+                (Track::NE, Dir4::S) => cart.dir = Dir4::W,
+                (Track::NE, Dir4::E) => cart.dir = Dir4::N,
+                (Track::NE, Dir4::W) => cart.dir = Dir4::S,
                 (Track::Int, dir) => {
-                    cart.dir = dir.next_dir(cart.intersections_seen);
+                    cart.dir = next_dir(dir, cart.intersections_seen);
                     cart.intersections_seen += 1;
                 }
                 _ => {}
@@ -260,24 +154,24 @@ impl Mines {
         None
     }
 
-    fn step_v2(&mut self) -> Option<Pt> {
+    fn step_v2(&mut self) -> Option<Pt<usize>> {
         self.carts.sort();
         let mut indices_to_remove = Vec::new();
         for i in 0..self.carts.len() {
             let cart = &mut self.carts[i];
             cart.loc += cart.dir;
-            match (self.map[&cart.loc], cart.dir) {
-                (Track::Hor, Dir::N | Dir::S) | (Track::Ver, Dir::E | Dir::W) => unreachable!(),
-                (Track::NW, Dir::N) => cart.dir = Dir::W,
-                (Track::NW, Dir::S) => cart.dir = Dir::E,
-                (Track::NW, Dir::E) => cart.dir = Dir::S,
-                (Track::NW, Dir::W) => cart.dir = Dir::N,
-                (Track::NE, Dir::N) => cart.dir = Dir::E,
-                (Track::NE, Dir::S) => cart.dir = Dir::W,
-                (Track::NE, Dir::E) => cart.dir = Dir::N,
-                (Track::NE, Dir::W) => cart.dir = Dir::S,
+            match (self.map[cart.loc], cart.dir) {
+                (Track::Hor, Dir4::N | Dir4::S) | (Track::Ver, Dir4::E | Dir4::W) => unreachable!(),
+                (Track::NW, Dir4::N) => cart.dir = Dir4::W,
+                (Track::NW, Dir4::S) => cart.dir = Dir4::E,
+                (Track::NW, Dir4::E) => cart.dir = Dir4::S,
+                (Track::NW, Dir4::W) => cart.dir = Dir4::N,
+                (Track::NE, Dir4::N) => cart.dir = Dir4::E,
+                (Track::NE, Dir4::S) => cart.dir = Dir4::W,
+                (Track::NE, Dir4::E) => cart.dir = Dir4::N,
+                (Track::NE, Dir4::W) => cart.dir = Dir4::S,
                 (Track::Int, dir) => {
-                    cart.dir = dir.next_dir(cart.intersections_seen);
+                    cart.dir = next_dir(dir, cart.intersections_seen);
                     cart.intersections_seen += 1;
                 }
                 _ => {}
@@ -316,20 +210,20 @@ impl Mines {
     }
 }
 
-pub fn part_one(input: &str) -> Option<Pt> {
-    let mut mines = input.parse::<Mines>().unwrap();
+pub fn part_one(input: &str) -> Result<Pt<usize>> {
+    let mut mines = input.parse::<Mines>().context(Failure::Parse)?;
     loop {
         if let Some(p) = mines.step() {
-            return Some(p);
+            return Ok(p);
         }
     }
 }
 
-pub fn part_two(input: &str) -> Option<Pt> {
-    let mut mines = input.parse::<Mines>().unwrap();
+pub fn part_two(input: &str) -> Result<Pt<usize>> {
+    let mut mines = input.parse::<Mines>().context(Failure::Parse)?;
     loop {
         if let Some(p) = mines.step_v2() {
-            return Some(p);
+            return Ok(p);
         }
     }
 }
@@ -341,7 +235,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(Pt(7, 3)));
+        assert_eq!(result.unwrap(), Pt { x: 7, y: 3 });
     }
 
     #[test]
@@ -349,6 +243,6 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
         ));
-        assert_eq!(result, Some(Pt(6, 4)));
+        assert_eq!(result.unwrap(), Pt { x: 6, y: 4 });
     }
 }
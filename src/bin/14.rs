@@ -273,77 +273,5 @@ pub fn part_two(input: &str) -> Option<usize> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(
-            result,
-            Some([5, 1, 5, 8, 9, 1, 6, 7, 7, 9].try_into().unwrap())
-        );
-    }
-
-    #[test]
-    fn test_part_one_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(
-            result,
-            Some([0, 1, 2, 4, 5, 1, 5, 8, 9, 1].try_into().unwrap())
-        );
-    }
-
-    #[test]
-    fn test_part_one_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(
-            result,
-            Some([9, 2, 5, 1, 0, 7, 1, 0, 8, 5].try_into().unwrap())
-        );
-    }
-
-    #[test]
-    fn test_part_one_three() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(
-            result,
-            Some([5, 9, 4, 1, 4, 2, 9, 8, 8, 2].try_into().unwrap())
-        );
-    }
-
-    #[test]
-    fn test_part_two_four() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(9));
-    }
-
-    #[test]
-    fn test_part_two_five() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
-        ));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two_six() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 6,
-        ));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_two_seven() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 7,
-        ));
-        assert_eq!(result, Some(2018));
-    }
+    advent_of_code::example_tests!();
 }
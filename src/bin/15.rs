@@ -1,34 +1,13 @@
 use std::{
     collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     fmt::Display,
-    ops::{Add, Index, IndexMut},
     str::FromStr,
 };
 
+use advent_of_code::util::{grid::Grid, point::Pt};
 use anyhow::anyhow;
 
-advent_of_code::solution!(15);
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-struct Pt(usize, usize);
-
-impl PartialOrd for Pt {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for Pt {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.1.cmp(&other.1).then_with(|| self.0.cmp(&other.0))
-    }
-}
-
-impl Pt {
-    fn h(&self, other: Pt) -> usize {
-        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
-    }
-}
+advent_of_code::solution!(15, parse = parse);
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
 enum Square {
@@ -37,23 +16,6 @@ enum Square {
     Wall,
 }
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-struct Map(Vec<Vec<Square>>);
-
-impl Index<Pt> for Map {
-    type Output = Square;
-
-    fn index(&self, index: Pt) -> &Self::Output {
-        &self.0[index.1][index.0]
-    }
-}
-
-impl IndexMut<Pt> for Map {
-    fn index_mut(&mut self, index: Pt) -> &mut Self::Output {
-        &mut self.0[index.1][index.0]
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 enum Race {
     Goblin,
@@ -63,23 +25,21 @@ enum Race {
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 struct Player {
     race: Race,
-    location: Pt,
+    location: Pt<usize>,
     hp: u8,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
-struct Battle {
-    map: &'static Map,
-    width: usize,
-    height: usize,
+pub struct Battle {
+    map: &'static Grid<Square>,
     players: Vec<Player>,
-    player_map: HashMap<Pt, usize>,
+    player_map: HashMap<Pt<usize>, usize>,
     killed_players: Vec<usize>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 struct Node {
-    p: Pt,
+    p: Pt<usize>,
     score: usize,
 }
 
@@ -96,7 +56,7 @@ impl Ord for Node {
 }
 
 impl Battle {
-    fn connected(&self, p1: Pt, p2: Pt) -> bool {
+    fn connected(&self, p1: Pt<usize>, p2: Pt<usize>) -> bool {
         let mut to_do = VecDeque::new();
         let mut done = HashSet::new();
         to_do.push_back(p1);
@@ -114,19 +74,14 @@ impl Battle {
         false
     }
 
-    fn neighbors(&self, pt: Pt) -> impl Iterator<Item = Pt> + '_ {
-        (pt.0.saturating_sub(1)..=pt.0.add(1).min(self.height - 1))
-            .flat_map(move |x| {
-                (pt.1.saturating_sub(1)..=pt.1.add(1).min(self.width - 1)).map(move |y| Pt(x, y))
-            })
-            .filter(move |&p| {
-                self.map[p] != Square::Wall
-                    && !self.player_map.contains_key(&p)
-                    && ((p.0 == pt.0 && p.1 != pt.1) || (p.1 == pt.1 && p.0 != pt.0))
-            })
+    fn neighbors(&self, pt: Pt<usize>) -> impl Iterator<Item = Pt<usize>> + '_ {
+        self.map
+            .neighbors4(pt)
+            .filter(|&p| self.map[p] != Square::Wall)
+            .filter(|p| !self.player_map.contains_key(p))
     }
 
-    fn next_move(&self, player: Player) -> Option<Pt> {
+    fn next_move(&self, player: Player) -> Option<Pt<usize>> {
         let target = match player.race {
             Race::Goblin => Race::Elf,
             Race::Elf => Race::Goblin,
@@ -147,23 +102,17 @@ impl Battle {
             .map(|(n, _, _)| n)
     }
 
-    fn neighboring_enemy(&self, player: Player) -> Option<Pt> {
+    fn neighboring_enemy(&self, player: Player) -> Option<Pt<usize>> {
         let target = match player.race {
             Race::Goblin => Race::Elf,
             Race::Elf => Race::Goblin,
         };
-        (player.location.0.saturating_sub(1)..=player.location.0.add(1).min(self.height - 1))
-            .flat_map(move |x| {
-                (player.location.1.saturating_sub(1)..=player.location.1.add(1).min(self.width - 1))
-                    .map(move |y| Pt(x, y))
-            })
-            .filter(move |&p| {
-                ((p.0 == player.location.0 && p.1 != player.location.1)
-                    || (p.1 == player.location.1 && p.0 != player.location.0))
-                    && self
-                        .player_map
-                        .get(&p)
-                        .is_some_and(|&pi| self.players[pi].race == target)
+        self.map
+            .neighbors4(player.location)
+            .filter(|p| {
+                self.player_map
+                    .get(p)
+                    .is_some_and(|&pi| self.players[pi].race == target)
             })
             .min_by(|p1, p2| {
                 self.players[self.player_map[p1]]
@@ -173,20 +122,20 @@ impl Battle {
             })
     }
 
-    fn a_star(&self, start: Pt, goal: Pt) -> Option<Vec<Pt>> {
+    fn a_star(&self, start: Pt<usize>, goal: Pt<usize>) -> Option<Vec<Pt<usize>>> {
         if start == goal {
             return Some(vec![goal]);
         }
         if !self.connected(start, goal) {
             return None;
         }
-        let h = |p| goal.h(p);
+        let h = |p: Pt<usize>| goal.manhattan(&p);
         let mut open_set = BinaryHeap::new();
         open_set.push(Node {
             p: start,
             score: h(start),
         });
-        let mut came_from: HashMap<Pt, Pt> = HashMap::new();
+        let mut came_from: HashMap<Pt<usize>, Pt<usize>> = HashMap::new();
         let mut g_score = HashMap::new();
         g_score.insert(start, 0);
         while let Some(current) = open_set.pop() {
@@ -223,7 +172,7 @@ impl Battle {
             .filter(|(i, _)| !self.killed_players.contains(i))
             .all(|(_, p)| p.race == Race::Goblin)
         {
-            println!("Goblins win!");
+            advent_of_code::outln!("Goblins win!");
             Some((
                 Race::Goblin,
                 self.players
@@ -240,7 +189,7 @@ impl Battle {
             .filter(|(i, _)| !self.killed_players.contains(i))
             .all(|(_, p)| p.race == Race::Elf)
         {
-            println!("Elves win!");
+            advent_of_code::outln!("Elves win!");
             Some((
                 Race::Elf,
                 self.players
@@ -257,7 +206,7 @@ impl Battle {
 
     fn play(&mut self) -> u32 {
         for round in 0.. {
-            println!("Round {round}:\n{self}");
+            advent_of_code::outln!("Round {round}:\n{self}");
             let mut indices = (0..self.players.len())
                 .filter(|i| !self.killed_players.contains(i))
                 .collect::<Vec<_>>();
@@ -303,7 +252,7 @@ impl Battle {
 
     fn play_with_attack_power(&mut self, power: u8) -> Option<u32> {
         for round in 0.. {
-            println!("Round {round}:\n{self}");
+            advent_of_code::outln!("Round {round}:\n{self}");
             let mut indices = (0..self.players.len())
                 .filter(|i| !self.killed_players.contains(i))
                 .collect::<Vec<_>>();
@@ -371,36 +320,23 @@ impl FromStr for Battle {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let height = s.lines().count();
-        let width = s.lines().next().unwrap().len();
-        let mut map = Box::new(Map(vec![Vec::with_capacity(width); height]));
         let mut players = Vec::new();
-        for ((y, line), l) in s.lines().enumerate().zip(&mut map.0) {
-            for (x, c) in line.char_indices() {
-                match c {
-                    '#' => l.push(Square::Wall),
-                    '.' => l.push(Square::Open),
-                    'G' => {
-                        l.push(Square::Open);
-                        players.push(Player {
-                            race: Race::Goblin,
-                            location: Pt(x, y),
-                            hp: 200,
-                        });
-                    }
-                    'E' => {
-                        l.push(Square::Open);
-                        players.push(Player {
-                            race: Race::Elf,
-                            location: Pt(x, y),
-                            hp: 200,
-                        });
-                    }
-                    _ => return Err(anyhow!("{c} is not a valid token")),
-                }
-            }
-        }
-        let map = Box::leak(map);
+        let map = Grid::parse(s, |pt, c| {
+            let race = match c {
+                '#' => return Ok(Square::Wall),
+                '.' => return Ok(Square::Open),
+                'G' => Race::Goblin,
+                'E' => Race::Elf,
+                _ => return Err(anyhow!("{c} is not a valid token")),
+            };
+            players.push(Player {
+                race,
+                location: pt,
+                hp: 200,
+            });
+            Ok(Square::Open)
+        })?;
+        let map = Box::leak(Box::new(map));
         let mut player_map = HashMap::with_capacity(players.len());
         for (index, player) in players.iter().enumerate() {
             player_map.insert(player.location, index);
@@ -408,8 +344,6 @@ impl FromStr for Battle {
         let killed_players = Vec::new();
         Ok(Self {
             map,
-            width,
-            height,
             players,
             player_map,
             killed_players,
@@ -419,38 +353,36 @@ impl FromStr for Battle {
 
 impl Display for Battle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (y, line) in self.map.0.iter().enumerate() {
-            for (x, c) in line.iter().enumerate() {
-                let c = if c == &Square::Wall {
-                    '#'
-                } else if let Some(p) = self
-                    .players
-                    .iter()
-                    .enumerate()
-                    .find(|(i, p)| p.location == Pt(x, y) && !self.killed_players.contains(i))
-                {
-                    match p.1.race {
-                        Race::Goblin => 'G',
-                        Race::Elf => 'E',
-                    }
-                } else {
-                    '.'
-                };
-                write!(f, "{c}")?;
+        let map = self.map.render(|pt, square| {
+            if square == &Square::Wall {
+                '#'
+            } else if let Some(p) = self
+                .players
+                .iter()
+                .enumerate()
+                .find(|(i, p)| p.location == pt && !self.killed_players.contains(i))
+            {
+                match p.1.race {
+                    Race::Goblin => 'G',
+                    Race::Elf => 'E',
+                }
+            } else {
+                '.'
             }
-            writeln!(f)?;
-        }
-        Ok(())
+        });
+        write!(f, "{map}")
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut battle = input.trim().parse::<Battle>().unwrap();
-    Some(battle.play())
+pub fn parse(input: &str) -> anyhow::Result<Battle> {
+    input.trim().parse()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let battle = input.trim().parse::<Battle>().unwrap();
+pub fn part_one(battle: &Battle) -> Option<u32> {
+    Some(battle.clone().play())
+}
+
+pub fn part_two(battle: &Battle) -> Option<u32> {
     (4..).find_map(|i| battle.clone().play_with_attack_power(i))
 }
 
@@ -458,87 +390,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(27730));
-    }
-
-    #[test]
-    fn test_part_one_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(36334));
-    }
-
-    #[test]
-    fn test_part_one_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(39514));
-    }
-
-    #[test]
-    fn test_part_one_three() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(27755));
-    }
-
-    #[test]
-    fn test_part_one_four() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(28944));
-    }
-
-    #[test]
-    fn test_part_one_five() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
-        ));
-        assert_eq!(result, Some(18740));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4988));
-    }
-
-    #[test]
-    fn test_part_two_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(31284));
-    }
-
-    #[test]
-    fn test_part_two_three() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(3478));
-    }
-
-    #[test]
-    fn test_part_two_four() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(6474));
-    }
-
-    #[test]
-    fn test_part_two_five() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 5,
-        ));
-        assert_eq!(result, Some(1140));
-    }
+    advent_of_code::example_tests!();
 }
@@ -1,14 +1,9 @@
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
+use advent_of_code::util::{geom::Rect, grid::SparseGrid, point::Pt};
 use anyhow::anyhow;
 
-advent_of_code::solution!(17);
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-struct Pt {
-    x: usize,
-    y: usize,
-}
+advent_of_code::solution!(17, parse = parse);
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 enum Soil {
@@ -24,71 +19,55 @@ impl Soil {
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
-struct Clay {
-    map: HashMap<Pt, Soil>,
-    min: Pt,
-    max: Pt,
+pub struct Clay {
+    map: SparseGrid<Soil>,
+    bounds: Rect<usize>,
 }
 
 impl FromStr for Clay {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = HashMap::new();
+        let mut map = SparseGrid::new();
+        let mut bounds: Option<Rect<usize>> = None;
         let err = |s: &str| anyhow!("Malformed input {s}");
-        let mut min = Pt {
-            x: usize::MAX,
-            y: usize::MAX,
-        };
-        let mut max = Pt {
-            x: usize::MIN,
-            y: usize::MIN,
-        };
         for line in s.lines() {
             let (pt1, pt2) = line.split_once(", ").ok_or(err(line))?;
             let (var, val) = pt1.split_once('=').ok_or(err(pt1))?;
-            match var {
+            let val = val.parse()?;
+            let (_, range) = pt2.split_once('=').ok_or(err(pt2))?;
+            let (from, to) = range
+                .split_once("..")
+                .ok_or(err(range))
+                .and_then(|(from, to)| Ok((from.parse()?, to.parse()?)))?;
+            let vein = match var {
                 "x" => {
-                    let x = val.parse()?;
-                    let (_, range) = pt2.split_once('=').ok_or(err(pt2))?;
-                    let (from, to) = range
-                        .split_once("..")
-                        .ok_or(err(range))
-                        .and_then(|(from, to)| Ok((from.parse()?, to.parse()?)))?;
-                    for y in from..=to {
-                        min.x = min.x.min(x);
-                        min.y = min.y.min(y);
-                        max.x = max.x.max(x);
-                        max.y = max.y.max(y);
-                        map.insert(Pt { x, y }, Soil::Clay);
-                    }
-                }
-                "y" => {
-                    let y = val.parse()?;
-                    let (_, range) = pt2.split_once('=').ok_or(err(pt2))?;
-                    let (from, to) = range
-                        .split_once("..")
-                        .ok_or(err(range))
-                        .and_then(|(from, to)| Ok((from.parse()?, to.parse()?)))?;
-                    for x in from..=to {
-                        map.insert(Pt { x, y }, Soil::Clay);
-                    }
+                    let vein = Rect::new(Pt { x: val, y: from }, Pt { x: val, y: to })
+                        .ok_or(err(range))?;
+                    // the scanned area is bounded by the x= veins only
+                    bounds = Some(bounds.map_or(vein, |b| b.union(&vein)));
+                    vein
                 }
+                "y" => Rect::new(Pt { x: from, y: val }, Pt { x: to, y: val }).ok_or(err(range))?,
                 _ => return Err(err(var)),
+            };
+            for pt in vein.points() {
+                map.insert(pt, Soil::Clay);
             }
         }
-        Ok(Self { map, max, min })
+        let bounds = bounds.ok_or(anyhow!("No x= clay veins"))?;
+        Ok(Self { map, bounds })
     }
 }
 
 impl Display for Clay {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in self.min.y..=self.max.y {
-            for x in self.min.x..=self.max.x {
+        for y in self.bounds.min.y..=self.bounds.max.y {
+            for x in self.bounds.min.x..=self.bounds.max.x {
                 let c = if (x, y) == (500, 0) {
                     '+'
                 } else {
-                    match self.map.get(&Pt { x, y }) {
+                    match self.map.get(Pt { x, y }) {
                         Some(Soil::WaterDown) => '|',
                         Some(Soil::WaterSideways) => '~',
                         Some(Soil::Clay) => '#',
@@ -116,13 +95,13 @@ impl Clay {
         self.map.insert(
             Pt {
                 x: 500,
-                y: self.min.y,
+                y: self.bounds.min.y,
             },
             Soil::WaterDown,
         );
         let mut todo_down = vec![Pt {
             x: 500,
-            y: self.min.y,
+            y: self.bounds.min.y,
         }];
         let mut todo_sideways = Vec::new();
         loop {
@@ -132,10 +111,10 @@ impl Clay {
             for w in todo_down.drain(..) {
                 let mut it = self.vertical_to(w).peekable();
                 while let Some(y) = it.next() {
-                    if it.peek().is_none() && y != self.max.y {
+                    if it.peek().is_none() && y != self.bounds.max.y {
                         if self
                             .map
-                            .get(&Pt { x: w.x, y: y + 1 })
+                            .get(Pt { x: w.x, y: y + 1 })
                             .is_some_and(|&c| c == Soil::Clay)
                             || self.find_span(Pt { x: w.x, y }).0 == Bounded::BothSides
                         {
@@ -195,13 +174,13 @@ impl Clay {
         }
     }
 
-    fn find_span(&self, pt: Pt) -> (Bounded, usize, usize) /* (bounded, from, to) */ {
+    fn find_span(&self, pt: Pt<usize>) -> (Bounded, usize, usize) /* (bounded, from, to) */ {
         let (from, left_bounded) = (1..)
             .map(|delta| pt.x - delta)
             .find_map(|x| {
                 if self
                     .map
-                    .get(&Pt { x, y: pt.y })
+                    .get(Pt { x, y: pt.y })
                     .is_some_and(|&c| c == Soil::Clay)
                 {
                     Some((x + 1, true))
@@ -217,7 +196,7 @@ impl Clay {
             .find_map(|x| {
                 if self
                     .map
-                    .get(&Pt { x, y: pt.y })
+                    .get(Pt { x, y: pt.y })
                     .is_some_and(|&c| c == Soil::Clay)
                 {
                     Some((x - 1, true))
@@ -237,32 +216,32 @@ impl Clay {
         (bounded, from, to)
     }
 
-    fn supported(&self, pt: Pt) -> bool {
-        self.map.contains_key(&Pt {
+    fn supported(&self, pt: Pt<usize>) -> bool {
+        self.map.contains(Pt {
             x: pt.x,
             y: pt.y + 1,
         })
     }
 
-    fn vertical_to(&self, pt: Pt) -> impl Iterator<Item = usize> {
+    fn vertical_to(&self, pt: Pt<usize>) -> impl Iterator<Item = usize> {
         pt.y + 1
             ..(pt.y + 1..)
-                .find(|&y| self.map.contains_key(&Pt { x: pt.x, y }) || y > self.max.y)
-                .unwrap_or(self.max.y + 1)
+                .find(|&y| self.map.contains(Pt { x: pt.x, y }) || y > self.bounds.max.y)
+                .unwrap_or(self.bounds.max.y + 1)
     }
 
     fn count_water(&self) -> u32 {
         self.map
             .iter()
-            .filter(|(k, v)| (self.min.y..=self.max.y).contains(&k.y) && v.is_water())
+            .filter(|(k, v)| (self.bounds.min.y..=self.bounds.max.y).contains(&k.y) && v.is_water())
             .count() as u32
     }
 
     fn start_stop(&self, line: usize) -> Vec<(usize, usize)> {
         let mut seen_water: Option<usize> = None;
         let mut out = Vec::new();
-        for x in self.min.x..=self.max.x {
-            match (self.map.get(&Pt { x, y: line }), seen_water) {
+        for x in self.bounds.min.x..=self.bounds.max.x {
+            match (self.map.get(Pt { x, y: line }), seen_water) {
                 (None | Some(Soil::Clay), Some(start)) => {
                     out.push((start, x));
                     seen_water = None;
@@ -279,7 +258,7 @@ impl Clay {
 
     fn drain(&mut self) {
         self.map.retain(|_, v| *v != Soil::WaterDown);
-        for y in self.min.y..self.max.y {
+        for y in self.bounds.min.y..self.bounds.max.y {
             for (start, stop) in self.start_stop(y) {
                 if self
                     .find_span(Pt {
@@ -290,7 +269,7 @@ impl Clay {
                     != Bounded::BothSides
                 {
                     for x in start..stop {
-                        if let Some((_, e)) = self.map.remove_entry(&Pt { x, y }) {
+                        if let Some(e) = self.map.remove(Pt { x, y }) {
                             assert!(e.is_water());
                         }
                     }
@@ -300,14 +279,18 @@ impl Clay {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut clay = input.parse::<Clay>().unwrap();
+pub fn parse(input: &str) -> anyhow::Result<Clay> {
+    input.parse()
+}
+
+pub fn part_one(clay: &Clay) -> Option<u32> {
+    let mut clay = clay.clone();
     clay.fill();
     Some(clay.count_water())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut clay = input.parse::<Clay>().unwrap();
+pub fn part_two(clay: &Clay) -> Option<u32> {
+    let mut clay = clay.clone();
     clay.fill();
     clay.drain();
     Some(clay.count_water())
@@ -319,13 +302,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(57));
+        let clay = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_one(&clay), Some(57));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(29));
+        let clay = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_two(&clay), Some(29));
     }
 }
@@ -1,12 +1,9 @@
-use std::{
-    collections::HashMap,
-    ops::{Add, Index, IndexMut},
-    str::FromStr,
-};
+use std::{collections::HashMap, str::FromStr};
 
+use advent_of_code::util::{grid::Grid, point::Pt};
 use anyhow::anyhow;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(18, parse = parse);
 
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
@@ -42,60 +39,14 @@ impl TryFrom<char> for Acre {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-struct Pt {
-    x: usize,
-    y: usize,
-}
-
-impl Index<Pt> for LumberYard {
-    type Output = Acre;
-
-    fn index(&self, index: Pt) -> &Self::Output {
-        assert!(index.y < self.height && index.x < self.width);
-        &self.yard[index.y][index.x]
-    }
-}
-
-impl IndexMut<Pt> for LumberYard {
-    fn index_mut(&mut self, index: Pt) -> &mut Self::Output {
-        assert!(index.y < self.height && index.x < self.width);
-        &mut self.yard[index.y][index.x]
-    }
-}
-
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
-struct LumberYard {
-    yard: Vec<Vec<Acre>>,
-    width: usize,
-    height: usize,
-}
+pub struct LumberYard(Grid<Acre>);
 
 impl FromStr for LumberYard {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut yard = Vec::with_capacity(50);
-        let mut width = None;
-        for line in s.lines() {
-            let mut row = Vec::with_capacity(50);
-            for c in line.chars() {
-                row.push(c.try_into()?);
-            }
-            if let Some(w) = width {
-                assert_eq!(w, row.len());
-            } else {
-                width = Some(row.len());
-            }
-            yard.push(row);
-        }
-
-        let height = yard.len();
-        Ok(Self {
-            yard,
-            width: width.unwrap(),
-            height,
-        })
+        Ok(Self(s.parse()?))
     }
 }
 
@@ -107,20 +58,10 @@ struct NeighborCounts {
 }
 
 impl LumberYard {
-    fn neighbors(&self, pt: Pt) -> impl Iterator<Item = &Acre> + '_ {
-        assert!(pt.y < self.height && pt.x < self.width);
-        (pt.y.saturating_sub(1)..pt.y.add(2).min(self.height))
-            .flat_map(move |y| {
-                (pt.x.saturating_sub(1)..pt.x.add(2).min(self.width)).map(move |x| Pt { x, y })
-            })
-            .filter(move |&p| p.x != pt.x || p.y != pt.y)
-            .map(|pt| &self[pt])
-    }
-
-    fn neighbor_count(&self, pt: Pt) -> NeighborCounts {
+    fn neighbor_count(&self, pt: Pt<usize>) -> NeighborCounts {
         let mut counts = NeighborCounts::default();
-        for n in self.neighbors(pt) {
-            match n {
+        for n in self.0.neighbors8(pt) {
+            match self.0[n] {
                 Acre::Open => counts.open += 1,
                 Acre::Trees => counts.trees += 1,
                 Acre::Yard => counts.yard += 1,
@@ -129,15 +70,16 @@ impl LumberYard {
         counts
     }
 
+    fn step(&self, next: &mut Self) {
+        for (pt, a) in self.0.iter() {
+            next.0[pt] = a.next(self.neighbor_count(pt));
+        }
+    }
+
     fn process_steps(&mut self, num: usize) {
         let mut temporary = self.clone();
         for _ in 0..num {
-            for (pt, a) in (0..self.height)
-                .flat_map(|y| (0..self.width).map(move |x| Pt { x, y }))
-                .map(|p| (p, self[p]))
-            {
-                temporary[pt] = a.next(self.neighbor_count(pt));
-            }
+            self.step(&mut temporary);
             std::mem::swap(self, &mut temporary);
         }
     }
@@ -146,46 +88,35 @@ impl LumberYard {
         let mut temporary = self.clone();
         let mut seen = HashMap::new();
         for i in 0..num {
-            if let Some(already) = seen.insert(self.yard.clone(), i) {
+            if let Some(already) = seen.insert(self.0.clone(), i) {
                 let remaining = num - i;
                 let cycle_len = i - already;
                 self.process_steps(remaining % cycle_len);
                 return;
             }
-            for (pt, a) in (0..self.height)
-                .flat_map(|y| (0..self.width).map(move |x| Pt { x, y }))
-                .map(|p| (p, self[p]))
-            {
-                temporary[pt] = a.next(self.neighbor_count(pt));
-            }
+            self.step(&mut temporary);
             std::mem::swap(self, &mut temporary);
         }
     }
 
     fn resource_value(&self) -> u32 {
-        (self
-            .yard
-            .iter()
-            .flat_map(|i| i.iter())
-            .filter(|&&a| a == Acre::Trees)
-            .count()
-            * self
-                .yard
-                .iter()
-                .flat_map(|i| i.iter())
-                .filter(|&&a| a == Acre::Yard)
-                .count()) as u32
+        let count = |acre| self.0.values().filter(|&&a| a == acre).count();
+        (count(Acre::Trees) * count(Acre::Yard)) as u32
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut yard = input.parse::<LumberYard>().unwrap();
+pub fn parse(input: &str) -> anyhow::Result<LumberYard> {
+    input.parse()
+}
+
+pub fn part_one(yard: &LumberYard) -> Option<u32> {
+    let mut yard = yard.clone();
     yard.process_steps(10);
     Some(yard.resource_value())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut yard = input.parse::<LumberYard>().unwrap();
+pub fn part_two(yard: &LumberYard) -> Option<u32> {
+    let mut yard = yard.clone();
     yard.process_steps_smarter(1_000_000_000);
     Some(yard.resource_value())
 }
@@ -196,7 +127,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1147));
+        let yard = parse(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        assert_eq!(part_one(&yard), Some(1147));
     }
 }
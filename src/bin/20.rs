@@ -1,86 +1,23 @@
-use std::{
-    collections::HashMap,
-    ops::{Add, Not},
-};
+use std::collections::HashMap;
 
-use anyhow::anyhow;
+use advent_of_code::util::point::{Dir4, Pt};
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, params = Params);
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-struct Pt {
-    x: isize,
-    y: isize,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-enum Dir {
-    N,
-    S,
-    E,
-    W,
-}
-
-impl TryFrom<char> for Dir {
-    type Error = anyhow::Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            'N' => Ok(Self::N),
-            'S' => Ok(Self::S),
-            'E' => Ok(Self::E),
-            'W' => Ok(Self::W),
-            _ => Err(anyhow!("{value} is not a valid dir")),
-        }
-    }
-}
-
-#[allow(clippy::suspicious_arithmetic_impl)]
-impl Add<Dir> for Pt {
-    type Output = Pt;
-
-    fn add(self, rhs: Dir) -> Self::Output {
-        match rhs {
-            Dir::N => Pt {
-                x: self.x,
-                y: self.y - 1,
-            },
-            Dir::S => Pt {
-                x: self.x,
-                y: self.y + 1,
-            },
-            Dir::E => Pt {
-                x: self.x + 1,
-                y: self.y,
-            },
-            Dir::W => Pt {
-                x: self.x - 1,
-                y: self.y,
-            },
-        }
-    }
-}
-
-impl Not for Dir {
-    type Output = Dir;
-
-    fn not(self) -> Self::Output {
-        match self {
-            Dir::N => Dir::S,
-            Dir::S => Dir::N,
-            Dir::E => Dir::W,
-            Dir::W => Dir::E,
-        }
+advent_of_code::params! {
+    pub struct Params {
+        /// Minimum number of doors on the shortest path to the rooms counted in part two.
+        min_doors: u32 = 1000,
     }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 struct Map {
-    distances: HashMap<Pt, u32>,
+    distances: HashMap<Pt<isize>, u32>,
 }
 
 impl Map {
-    const ORIGIN: Pt = Pt { x: 0, y: 0 };
+    const ORIGIN: Pt<isize> = Pt { x: 0, y: 0 };
 
     fn construct(s: &str) -> Self {
         let mut out = Self::default();
@@ -105,8 +42,8 @@ impl Map {
                     cur_pos = *stack.last().unwrap();
                 }
                 'N' | 'S' | 'E' | 'W' => {
-                    let dir: Dir = c.try_into().unwrap();
-                    cur_pos = cur_pos + dir;
+                    let dir: Dir4 = c.try_into().unwrap();
+                    cur_pos += dir;
                     out.distances
                         .entry(cur_pos)
                         .and_modify(|d| *d = *d.min(&mut (previous_d + 1)))
@@ -128,55 +65,19 @@ impl Map {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, _: &Params) -> Option<u32> {
     let map = Map::construct(input);
     Some(map.longest_path_len())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let map = Map::construct(input);
-    Some(map.num_paths_larger_than(1000))
+    Some(map.num_paths_larger_than(params.min_doors))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
-    }
-
-    #[test]
-    fn test_part_one_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(10));
-    }
-
-    #[test]
-    fn test_part_one_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(18));
-    }
-
-    #[test]
-    fn test_part_one_three() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(23));
-    }
-
-    #[test]
-    fn test_part_one_four() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 4,
-        ));
-        assert_eq!(result, Some(31));
-    }
+    advent_of_code::example_tests!();
 }
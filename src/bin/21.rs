@@ -41,7 +41,7 @@ pub fn part_two(_input: &str) -> Option<u64> {
             return Some(f);
         }
         if let Some(old_i) = seen_vals.insert(f, i) {
-            println!("Last saw val {f} at {old_i}, index now is {i}");
+            advent_of_code::outln!("Last saw val {f} at {old_i}, index now is {i}");
         }
     }
     None
@@ -1,8 +1,6 @@
 advent_of_code::solution!(22);
 
-use std::collections::HashMap;
-
-use advent_of_code::util::{graph::Graph, point::Pt};
+use advent_of_code::util::{graph::Graph, grid::Grid, point::Pt};
 use strum::FromRepr;
 
 #[repr(usize)]
@@ -15,7 +13,7 @@ enum RegionType {
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 struct Cave {
-    map: HashMap<Pt<usize>, Region>,
+    map: Grid<Region>,
     depth: usize,
 }
 
@@ -71,20 +69,14 @@ impl Graph for Cave {
         std::iter::once((
             Node {
                 loc: node.loc,
-                tool: node.tool.other(self.map[&node.loc].ty),
+                tool: node.tool.other(self.map[node.loc].ty),
             },
             7,
         ))
         .chain(
-            (node.loc.y.saturating_sub(1)..=(node.loc.y + 1))
-                .flat_map(move |y| {
-                    (node.loc.x.saturating_sub(1)..=(node.loc.x + 1)).map(move |x| (x, y))
-                })
-                .filter(move |&(x, y)| {
-                    (x == node.loc.x && y != node.loc.y) || (x != node.loc.x && y == node.loc.y)
-                })
-                .map(|(x, y)| Pt { x, y })
-                .filter(move |pt| node.tool.compat(self.map[pt].ty))
+            self.map
+                .neighbors4(node.loc)
+                .filter(move |&pt| node.tool.compat(self.map[pt].ty))
                 .map(move |loc| {
                     (
                         Node {
@@ -108,64 +100,46 @@ impl Cave {
         const Y_MUL: usize = 48271;
 
         let erosion_level = |index| erosion_level(depth, index);
-        let map = HashMap::with_capacity(target.x * target.y * 16);
+        let mouth = Region {
+            geologic_index: 0,
+            erosion_level: erosion_level(0),
+            ty: RegionType::Rocky,
+        };
+        let map = Grid::new(target.x * 16 + 1, target.y * 2 + 1, mouth);
         let mut out = Self { map, depth };
-        out.map.insert(
-            (0, 0).into(),
-            Region {
-                geologic_index: 0,
-                erosion_level: erosion_level(0),
-                ty: RegionType::Rocky,
-            },
-        );
         for x in 1..=(target.x * 16) {
             let geologic_index = X_MUL * x;
             let erosion_level = erosion_level(geologic_index);
-            out.map.insert(
-                (x, 0).into(),
-                Region {
-                    geologic_index,
-                    erosion_level,
-                    ty: RegionType::from_repr(erosion_level % 3).unwrap(),
-                },
-            );
+            out.map[Pt { x, y: 0 }] = Region {
+                geologic_index,
+                erosion_level,
+                ty: RegionType::from_repr(erosion_level % 3).unwrap(),
+            };
         }
         for y in 1..=(target.y * 2) {
             let geologic_index = Y_MUL * y;
             let erosion_level = erosion_level(geologic_index);
-            out.map.insert(
-                (0, y).into(),
-                Region {
-                    geologic_index,
-                    erosion_level,
-                    ty: RegionType::from_repr(erosion_level % 3).unwrap(),
-                },
-            );
+            out.map[Pt { x: 0, y }] = Region {
+                geologic_index,
+                erosion_level,
+                ty: RegionType::from_repr(erosion_level % 3).unwrap(),
+            };
         }
         for y in 1..=(target.y * 2) {
             for x in 1..=(target.x * 16) {
-                let geologic_index = out.map[&Pt { x: x - 1, y }].erosion_level
-                    * out.map[&Pt { x, y: y - 1 }].erosion_level;
+                let geologic_index = out.map[Pt { x: x - 1, y }].erosion_level
+                    * out.map[Pt { x, y: y - 1 }].erosion_level;
                 let erosion_level = erosion_level(geologic_index);
-                if (Pt { x, y }) == target {
-                    out.map.insert(
-                        target,
-                        Region {
-                            geologic_index,
-                            erosion_level,
-                            ty: RegionType::Rocky,
-                        },
-                    );
+                let ty = if (Pt { x, y }) == target {
+                    RegionType::Rocky
                 } else {
-                    out.map.insert(
-                        (x, y).into(),
-                        Region {
-                            geologic_index,
-                            erosion_level,
-                            ty: RegionType::from_repr(erosion_level % 3).unwrap(),
-                        },
-                    );
-                }
+                    RegionType::from_repr(erosion_level % 3).unwrap()
+                };
+                out.map[Pt { x, y }] = Region {
+                    geologic_index,
+                    erosion_level,
+                    ty,
+                };
             }
         }
         out
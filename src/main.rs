@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answer, download, examples, read, scaffold, solve, time, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -6,9 +8,25 @@ use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Every scaffolded day, compiled into this binary so that `all` and `time` run in-process.
+/// The module list is generated by `build.rs` from the contents of `src/bin`.
+#[cfg(not(any(test, feature = "dhat-heap")))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+#[cfg(any(test, feature = "dhat-heap"))]
+mod solutions {
+    pub const SOLUTIONS: &[advent_of_code::template::Solution] = &[];
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::solve::SolutionArgs;
+    use advent_of_code::template::commands::time::CompareOptions;
+    use advent_of_code::template::{Day, Input, MultiRunOptions, ReportFormat, Year};
+    use std::num::NonZeroUsize;
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -17,43 +35,84 @@ mod args {
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            force: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            watch: bool,
+            args: SolutionArgs,
         },
         All {
-            release: bool,
+            options: MultiRunOptions,
+            format: Option<ReportFormat>,
+        },
+        AnswerRecord {
+            day: Day,
+            part: Option<u8>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<CompareOptions>,
+            options: MultiRunOptions,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parses the command and the year it applies to. The year is `None` for the default year.
+    pub fn parse() -> Result<(Option<Year>, AppArguments), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let year = Year::namespace(args.opt_value_from_str("--year")?);
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                options: parse_multi_run_options(&mut args)?,
+                format: args.opt_value_from_str("--format")?,
+            },
+            Some("answer") => match args.subcommand()?.as_deref() {
+                Some("record") => AppArguments::AnswerRecord {
+                    part: args.opt_value_from_str("--part")?,
+                    day: args.free_from_str()?,
+                },
+                Some(x) => {
+                    eprintln!("Unknown answer command: {x}");
+                    process::exit(1);
+                }
+                None => {
+                    eprintln!("No answer command specified. Try `answer record <day>`.");
+                    process::exit(1);
+                }
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let options = parse_multi_run_options(&mut args)?;
+                let compare =
+                    (args.contains("--compare") || baseline.is_some()).then(|| CompareOptions {
+                        baseline,
+                        threshold: threshold.unwrap_or(10.0),
+                    });
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare,
+                    options,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -62,15 +121,27 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("examples") => AppArguments::Examples {
+                force: args.contains("--force"),
                 day: args.free_from_str()?,
+            },
+            Some("scaffold") => AppArguments::Scaffold {
                 download: args.contains("--download"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
+                day: args.free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
+                args: SolutionArgs {
+                    json: args.contains("--json"),
+                    submit_part: args.opt_value_from_str("--submit")?,
+                    params: args.values_from_str("--param")?,
+                    input: parse_input(&mut args)?,
+                },
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -89,7 +160,45 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
+    }
+
+    /// Parses `--jobs <n>`, the number of days to run in parallel, and `--timeout <seconds>`,
+    /// after which a day is abandoned.
+    fn parse_multi_run_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<MultiRunOptions, Box<dyn std::error::Error>> {
+        let jobs: Option<NonZeroUsize> = args.opt_value_from_str("--jobs")?;
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        Ok(MultiRunOptions {
+            jobs: jobs.map_or(1, NonZeroUsize::get),
+            timeout: timeout.map(Duration::try_from_secs_f64).transpose()?,
+        })
+    }
+
+    /// Parses `--input <path|->` and `--example [k]`, which select the input of `solve`.
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<Input, Box<dyn std::error::Error>> {
+        let mut input_args = vec![];
+        if let Some(path) = args.opt_value_from_str::<_, String>("--input")? {
+            input_args.extend(["--input".to_string(), path]);
+        }
+        // `--example` may be followed by the number of an example.
+        match args.opt_value_from_str::<_, u8>("--example") {
+            Ok(Some(k)) => input_args.extend(["--example".to_string(), k.to_string()]),
+            Ok(None) => {}
+            // anything but a flag after `--example` has to be the number of an example.
+            Err(pico_args::Error::Utf8ArgumentParsingFailed { value, .. })
+                if !value.starts_with("--") =>
+            {
+                input_args.extend(["--example".to_string(), value]);
+            }
+            Err(_) => {
+                if args.contains("--example") {
+                    input_args.push("--example".to_string());
+                }
+            }
+        }
+        Ok(Input::parse(&input_args)?)
     }
 }
 
@@ -99,30 +208,63 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+        Ok((year, args)) => match args {
+            AppArguments::All { options, format } => {
+                all::handle(solutions::SOLUTIONS, year, options, format);
+            }
+            AppArguments::AnswerRecord { day, part } => {
+                answer::record(solutions::SOLUTIONS, year, day, part);
+            }
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                compare,
+                options,
+            } => time::handle(
+                solutions::SOLUTIONS,
+                year,
+                day,
+                all,
+                store,
+                compare,
+                options,
+            ),
+            AppArguments::Download { day } => download::handle(year, day),
+            AppArguments::Read { day } => read::handle(year, day),
+            AppArguments::Examples { day, force } => examples::handle(year, day, force),
+            AppArguments::Scaffold {
+                day,
+                download,
+                template,
+                answer_type,
+            } => {
+                // download first, so that the template can use the puzzle title.
                 if download {
-                    download::handle(day);
+                    download::handle(year, day);
                 }
+                scaffold::handle(year, day, template.as_deref(), answer_type.as_deref());
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                args,
+            } => {
+                if watch {
+                    watch::handle(year, day, release, &args);
+                } else {
+                    solve::handle(year, day, release, dhat, &args);
+                }
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        download::handle(year, day);
+                        scaffold::handle(year, day, None, None);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,192 @@
+/// Module that stores known-correct answers and checks solutions against them.
+use std::{collections::HashMap, fmt::Display, fs, io, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{data_folder, Day, Year};
+
+/// Whether an answer matches the recorded, known-correct one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    /// No answer has been recorded for this part yet.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "unknown" => Ok(Verdict::Unknown),
+            s => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// Known-correct answers of a single day, stored in `data/answers/<day>.json`
+/// (`data/<year>/answers/<day>.json` for namespaced years).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+fn get_answers_path(year: Option<Year>, day: Day) -> String {
+    format!("data/{}/{day}.json", data_folder(year, "answers"))
+}
+
+impl Answers {
+    /// Read the recorded answers of a day. If not present, returns empty answers.
+    pub fn read(year: Option<Year>, day: Day) -> Self {
+        match fs::read_to_string(get_answers_path(year, day)) {
+            Ok(s) => Answers::try_from(s).unwrap_or_else(|e| {
+                eprintln!("{}: {e}", get_answers_path(year, day));
+                Answers::default()
+            }),
+            Err(_) => Answers::default(),
+        }
+    }
+
+    /// Write the answers of a day to disk.
+    pub fn store(&self, year: Option<Year>, day: Day) -> Result<(), io::Error> {
+        fs::create_dir_all(format!("data/{}", data_folder(year, "answers")))?;
+        let mut file = fs::File::create(get_answers_path(year, day))?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// Record a single known-correct answer of a day, keeping the other part untouched.
+    pub fn record(year: Option<Year>, day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+        let mut answers = Answers::read(year, day);
+        *answers.get_mut(part) = Some(answer.to_string());
+        answers.store(year, day)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+
+    fn get_mut(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part_1,
+            _ => &mut self.part_2,
+        }
+    }
+
+    /// Check an answer against the recorded one. Missing answers count as incorrect.
+    pub fn verify(&self, part: u8, answer: Option<&str>) -> Verdict {
+        match self.get(part) {
+            None => Verdict::Unknown,
+            Some(expected) if answer == Some(expected) => Verdict::Correct,
+            Some(_) => Verdict::Incorrect,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let answer = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("expected `{key}` to be null or string.")),
+        };
+
+        Ok(Answers {
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Verdict};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        }
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.verify(1, Some("42")), Verdict::Correct);
+        assert_eq!(answers.verify(1, Some("41")), Verdict::Incorrect);
+        assert_eq!(answers.verify(1, None), Verdict::Incorrect);
+        assert_eq!(answers.verify(2, Some("1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let answers = get_mock_answers();
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn handles_missing_parts() {
+        let answers = Answers::try_from(r#"{ "part_2": "CABDFE" }"#.to_string()).unwrap();
+        assert_eq!(answers.part_1, None);
+        assert_eq!(answers.part_2, Some("CABDFE".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "part_1": 42 }"#.to_string()).unwrap();
+    }
+}
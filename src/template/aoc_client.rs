@@ -0,0 +1,572 @@
+/// Native client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions, and submits answers.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{data_folder, Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    MissingYear,
+    BadStatus(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/.adventofcode.session\"."
+            ),
+            AocClientError::MissingYear => write!(f, "no year found. Set `AOC_YEAR`."),
+            AocClientError::BadStatus(404) => {
+                write!(
+                    f,
+                    "the puzzle could not be found, it may not be unlocked yet."
+                )
+            }
+            AocClientError::BadStatus(400) => {
+                write!(
+                    f,
+                    "the server rejected the request, check the session cookie."
+                )
+            }
+            AocClientError::BadStatus(code) => {
+                write!(f, "the server responded with status code {code}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(code, _) => AocClientError::BadStatus(code),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionVerdict {
+    Correct,
+    /// The answer is wrong, possibly with a hint whether it is too high or too low,
+    /// and the time to wait before the next submission.
+    Incorrect {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// Answers were submitted too recently, the server asks to wait for the given time.
+    TooRecent {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+/// The outcome of a submission, along with the message shown by the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub verdict: SubmissionVerdict,
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION"),
+                " (native advent of code client)"
+            ))
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+        }
+    }
+
+    /// Create a client for `year` from the environment, defaulting to the year in `AOC_YEAR`.
+    /// The base url can be overridden with `AOC_URL`, e.g. to point it to a local server.
+    pub fn from_env(year: Option<Year>) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::MissingSession)?;
+        let year = year
+            .or_else(Year::from_env)
+            .ok_or(AocClientError::MissingYear)?;
+        let base_url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session, year.into_inner()))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    pub fn fetch_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetch the puzzle description as markdown. Contains part two once part one is solved.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        let articles: Vec<String> = articles(&html).map(html_to_markdown).collect();
+        Ok(articles.join("\n\n") + "\n")
+    }
+
+    pub fn submit_answer(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        let html = response.into_string()?;
+        let message = articles(&html)
+            .map(html_to_markdown)
+            .next()
+            .unwrap_or_default();
+        Ok(Submission {
+            verdict: parse_verdict(&message),
+            message,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the session cookie from `AOC_SESSION` or from the `.adventofcode.session` file
+/// in the home or config directory, the same locations aoc-cli uses.
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|h| h.join(".config")));
+
+    [
+        home.map(|h| h.join(".adventofcode.session")),
+        config.map(|c| c.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|s| s.trim().to_string())
+    .filter(|s| !s.is_empty())
+}
+
+fn get_input_path(year: Option<Year>, day: Day) -> String {
+    format!("data/{}/{day}.txt", data_folder(year, "inputs"))
+}
+
+fn get_puzzle_path(year: Option<Year>, day: Day) -> String {
+    format!("data/{}/{day}.md", data_folder(year, "puzzles"))
+}
+
+/// Download the input and puzzle description of a day to `data/inputs` and `data/puzzles`.
+pub fn download(year: Option<Year>, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(year)?;
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    fs::create_dir_all(data_folder_path(year, "inputs"))?;
+    fs::create_dir_all(data_folder_path(year, "puzzles"))?;
+
+    fs::write(&input_path, client.fetch_input(day)?)?;
+    fs::write(&puzzle_path, client.fetch_puzzle(day)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetch the puzzle description of a day, store it in `data/puzzles` and print it.
+pub fn read(year: Option<Year>, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(year)?;
+    let puzzle = client.fetch_puzzle(day)?;
+    fs::create_dir_all(data_folder_path(year, "puzzles"))?;
+    fs::write(get_puzzle_path(year, day), &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+pub fn submit(
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    result: &str,
+) -> Result<Submission, AocClientError> {
+    AocClient::from_env(year)?.submit_answer(day, part, result)
+}
+
+fn data_folder_path(year: Option<Year>, folder: &str) -> String {
+    format!("data/{}", data_folder(year, folder))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_verdict(message: &str) -> SubmissionVerdict {
+    if message.contains("That's the right answer") {
+        SubmissionVerdict::Correct
+    } else if message.contains("That's not the right answer") {
+        let hint = if message.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if message.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        SubmissionVerdict::Incorrect {
+            hint,
+            wait: parse_penalty(message),
+        }
+    } else if message.contains("You gave an answer too recently") {
+        SubmissionVerdict::TooRecent {
+            wait: parse_wait(message),
+        }
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionVerdict::WrongLevel
+    } else {
+        SubmissionVerdict::Unknown
+    }
+}
+
+/// Parses the remaining wait time from e.g. "You have 4m 30s left to wait."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (time, _) = rest.split_once(" left to wait")?;
+    time.split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "s" => Some(value),
+                "m" => Some(value * 60),
+                "h" => Some(value * 3600),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Parses the penalty after a wrong answer from e.g. "Please wait one minute before trying again."
+fn parse_penalty(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("lease wait ")?;
+    let mut words = rest.split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| Duration::from_secs(minutes * 60))
+}
+
+/// Iterates over the inner HTML of all `<article>` elements.
+fn articles(html: &str) -> impl Iterator<Item = &str> {
+    html.split("<article").skip(1).filter_map(|chunk| {
+        let (_, body) = chunk.split_once('>')?;
+        Some(body.split("</article>").next().unwrap_or(body))
+    })
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Converts the subset of HTML used in puzzle descriptions to markdown.
+/// Example blocks become fenced code blocks, `<em>` becomes `*emphasis*`.
+fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut in_code = false;
+    // set while an inline `<code>` has been opened but has no content yet.
+    let mut code_opened = false;
+    let mut em_in_code = false;
+    let mut links: Vec<String> = vec![];
+
+    while let Some(start) = rest.find('<') {
+        let text = decode_entities(&rest[..start]);
+        if !text.is_empty() {
+            code_opened = false;
+        }
+        out.push_str(&text);
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", false) if !in_pre => {
+                in_code = true;
+                code_opened = true;
+                out.push('`');
+            }
+            ("code", true) if !in_pre => {
+                in_code = false;
+                out.push('`');
+                if em_in_code {
+                    em_in_code = false;
+                    out.push('*');
+                }
+            }
+            // emphasis can't be expressed inside fenced blocks.
+            ("em", _) if in_pre => {}
+            ("em", false) if in_code && code_opened => {
+                // render `<code><em>x</em></code>` as *`x`*.
+                out.pop();
+                out.push_str("*`");
+                em_in_code = true;
+            }
+            ("em", true) if em_in_code => {}
+            ("em", _) => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    out.push_str(&decode_entities(rest));
+
+    // collapse runs of blank lines left by nested block elements.
+    let mut collapsed = out.trim().to_string();
+    while collapsed.contains("\n\n\n") {
+        collapsed = collapsed.replace("\n\n\n", "\n\n");
+    }
+    collapsed
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let (_, rest) = tag.split_once(&format!("{name}=\""))?;
+    let (value, _) = rest.split_once('"')?;
+    Some(decode_entities(value))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::{html_to_markdown, AocClient, Hint, SubmissionVerdict};
+    use crate::day;
+
+    /// Serve a single HTTP response on a local port and report the request back.
+    fn stub_server(status: u16, body: &str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let body = body.to_string();
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            tx.send(request).unwrap();
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+
+        (url, rx)
+    }
+
+    fn received(rx: &mpsc::Receiver<String>) -> String {
+        rx.recv_timeout(Duration::from_secs(5)).unwrap()
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, rx) = stub_server(200, "+1\n-2\n");
+        let client = AocClient::new(&url, "abc\n", 2018);
+        assert_eq!(client.fetch_input(day!(1)).unwrap(), "+1\n-2\n");
+
+        let request = received(&rx);
+        assert!(request.starts_with("GET /2018/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+    }
+
+    #[test]
+    fn fetches_puzzle() {
+        let (url, rx) = stub_server(
+            200,
+            r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p><pre><code>+1
+-2
+</code></pre><p>Result is <code><em>-1</em></code>.</p></article></main>"#,
+        );
+        let client = AocClient::new(&url, "abc", 2018);
+        let puzzle = client.fetch_puzzle(day!(1)).unwrap();
+        assert_eq!(
+            puzzle,
+            "## --- Day 1: Test ---\n\nFor example:\n\n```\n+1\n-2\n```\n\nResult is *`-1`*.\n"
+        );
+        assert!(received(&rx).starts_with("GET /2018/day/1 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, rx) = stub_server(
+            200,
+            "<article><p>That's not the right answer; your answer is too high. Please wait one minute.</p></article>",
+        );
+        let client = AocClient::new(&url, "abc", 2018);
+        let submission = client.submit_answer(day!(5), 2, "42").unwrap();
+        assert_eq!(
+            submission.verdict,
+            SubmissionVerdict::Incorrect {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60)),
+            }
+        );
+
+        let request = received(&rx);
+        assert!(request.starts_with("POST /2018/day/5/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        let (url, _rx) = stub_server(
+            200,
+            "<article><p>You gave an answer too recently. You have 4m 30s left to wait.</p></article>",
+        );
+        let client = AocClient::new(&url, "abc", 2018);
+        let submission = client.submit_answer(day!(5), 1, "42").unwrap();
+        assert_eq!(
+            submission.verdict,
+            SubmissionVerdict::TooRecent {
+                wait: Some(Duration::from_secs(270))
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_bad_status() {
+        let (url, _rx) = stub_server(404, "not found");
+        let client = AocClient::new(&url, "abc", 2018);
+        client.fetch_input(day!(25)).unwrap();
+    }
+
+    #[test]
+    fn converts_links_and_lists() {
+        let md = html_to_markdown(
+            r#"<p>See <a href="/2018/about">this</a> &amp; that:</p><ul><li><code>a &lt; b</code></li><li>c</li></ul>"#,
+        );
+        assert_eq!(md, "See [this](/2018/about) & that:\n\n- `a < b`\n- c");
+    }
+}
@@ -1,5 +1,111 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{collections::HashSet, process};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days, output,
+    run_multi::{run_multi, MultiRun},
+    Day, MultiRunOptions, ReportFormat, Solution, Year,
+};
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    options: MultiRunOptions,
+    format: Option<ReportFormat>,
+) {
+    let days = all_days().collect();
+    let run = match format {
+        Some(format) => {
+            let (run, report) = run_report(solutions, year, &days, options, format);
+            println!("{report}");
+            run
+        }
+        None => run_multi(solutions, year, &days, false, options),
+    };
+
+    if run.has_incorrect_answers() {
+        eprintln!("\nOne or more answers do not match the recorded answers.");
+    }
+    if run.has_failures() {
+        eprintln!("\nOne or more days timed out or panicked.");
+    }
+    if run.has_incorrect_answers() || run.has_failures() {
+        process::exit(1);
+    }
+}
+
+/// Runs the days and renders their report. The report replaces the regular output, including
+/// what the solutions print, so that it can be piped into other tools.
+fn run_report(
+    solutions: &[Solution],
+    year: Option<Year>,
+    days: &HashSet<Day>,
+    options: MultiRunOptions,
+    format: ReportFormat,
+) -> (MultiRun, String) {
+    let (run, _) = output::capture(|| run_multi(solutions, year, days, false, options));
+    let report = format.render(&run, year);
+    (run, report)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashSet, fs, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::run_report;
+    use crate::template::output;
+    use crate::template::record::{Measurement, PartRecord};
+    use crate::template::run_multi::run_multi;
+    use crate::template::{MultiRunOptions, ReportFormat, Solution};
+    use crate::{day, year};
+
+    #[test]
+    fn reports_valid_json_when_solutions_print() {
+        let solution = Solution {
+            year: Some(year!(2015)),
+            day: day!(25),
+            parts: &[1],
+            run: |input, _, _, _| {
+                crate::outln!("Round 1:\n{input}");
+                let time = Measurement::single(Duration::ZERO);
+                vec![PartRecord::new(day!(25), 1, Some(&"42"), time)]
+            },
+        };
+        // a year of its own, so that the input does not replace a real one.
+        fs::create_dir_all("data/2015/inputs").unwrap();
+        fs::write("data/2015/inputs/25.txt", "#.#").unwrap();
+
+        for options in [
+            MultiRunOptions::default(),
+            MultiRunOptions {
+                jobs: 2,
+                timeout: Some(Duration::from_secs(10)),
+            },
+        ] {
+            let (run, report) = run_report(
+                &[solution],
+                solution.year,
+                &HashSet::from([solution.day]),
+                options,
+                ReportFormat::Json,
+            );
+            assert_eq!(run.records.len(), 1);
+            assert!(report.parse::<JsonValue>().is_ok(), "{report}");
+
+            // what the solution printed is captured with the regular output, not printed.
+            let (_, output) = output::capture(|| {
+                run_multi(
+                    &[solution],
+                    solution.year,
+                    &HashSet::from([solution.day]),
+                    false,
+                    options,
+                )
+            });
+            assert!(output.contains("Round 1:\n#.#\n"), "{output}");
+        }
+
+        fs::remove_dir_all("data/2015").unwrap();
+    }
 }
@@ -0,0 +1,38 @@
+use std::process;
+
+use crate::template::record::{PartStatus, PARSE_PHASE};
+use crate::template::runner::{record_answer, RunOptions};
+use crate::template::{data_folder, find_solution, try_read_file, Day, Overrides, Solution, Year};
+
+/// Run the solution of a day and record its current answers as known-correct.
+pub fn record(solutions: &[Solution], year: Option<Year>, day: Day, part: Option<u8>) {
+    let Some(solution) = find_solution(solutions, year, day) else {
+        eprintln!("No solution found for day {day}.");
+        process::exit(1);
+    };
+
+    let input = match try_read_file(&data_folder(year, "inputs"), day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Failed to read input for day {day}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let parts: Vec<u8> = solution
+        .parts
+        .iter()
+        .copied()
+        .filter(|p| part.is_none_or(|part| *p == part))
+        .collect();
+    let records = (solution.run)(&input, &parts, &Overrides::new(), RunOptions::default());
+
+    println!("---");
+
+    for record in records.iter().filter(|r| r.part != PARSE_PHASE) {
+        match (&record.answer, record.status) {
+            (Some(answer), PartStatus::Solved) => record_answer(year, day, record.part, answer),
+            _ => println!("Part {} has no answer, skipping.", record.part),
+        }
+    }
+}
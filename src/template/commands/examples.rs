@@ -0,0 +1,65 @@
+use std::{fs, path::Path, process};
+
+use crate::template::examples::{example_file, extract, ExampleManifest};
+use crate::template::{data_folder, Day, Year};
+
+/// Extract the examples of a downloaded puzzle into `data/examples/<day>-<n>.txt` and write
+/// their expected answers to the day's manifest.
+pub fn handle(year: Option<Year>, day: Day, force: bool) {
+    let puzzle_path = format!("data/{}/{day}.md", data_folder(year, "puzzles"));
+    let example_folder = format!("data/{}", data_folder(year, "examples"));
+
+    let puzzle = match fs::read_to_string(&puzzle_path) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!(
+                "Failed to read puzzle \"{puzzle_path}\": {e}. Run `cargo read {day}` first."
+            );
+            process::exit(1);
+        }
+    };
+
+    let extracted = extract(day, &puzzle);
+
+    if extracted.inputs.is_empty() {
+        eprintln!("Could not find any examples in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    let paths: Vec<String> = (1..=extracted.inputs.len())
+        .map(|n| format!("{example_folder}/{}", example_file(day, n)))
+        .collect();
+
+    let exists = !ExampleManifest::read(year, day).examples.is_empty()
+        || paths.iter().any(|path| Path::new(path).exists());
+
+    if exists && !force {
+        eprintln!("Examples of day {day} already exist. Use `--force` to overwrite them.");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::create_dir_all(&example_folder) {
+        eprintln!("Failed to create example folder: {e}");
+        process::exit(1);
+    }
+
+    for (path, input) in paths.iter().zip(&extracted.inputs) {
+        if let Err(e) = fs::write(path, input) {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+        println!("Wrote example to \"{path}\"");
+    }
+
+    if let Err(e) = extracted.manifest.store(year, day) {
+        eprintln!("Failed to write example manifest: {e}");
+        process::exit(1);
+    }
+
+    for example in &extracted.manifest.examples {
+        println!(
+            "Part {}: \"{}\" is expected to yield {}",
+            example.part, example.file, example.expected
+        );
+    }
+}
@@ -1,30 +1,197 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
     process,
 };
 
-use crate::template::Day;
+use crate::template::{bin_name, data_folder, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Custom templates live in `templates/<name>.txt`. `templates/default.txt` replaces the built-in one.
+const TEMPLATES_FOLDER: &str = "templates";
+
+static EXAMPLES_START: &str = "%FOR_EACH_EXAMPLE%\n";
+static EXAMPLES_END: &str = "%END_FOR_EACH_EXAMPLE%\n";
+
+/// The values that are filled into the placeholders of a module template.
+pub struct TemplateContext {
+    pub year: Option<Year>,
+    pub day: Day,
+    pub title: Option<String>,
+    pub answer_type: String,
+    /// The numbers of the day's example files, `None` for the unnumbered `NN.txt`.
+    pub examples: Vec<Option<u8>>,
+}
+
+impl TemplateContext {
+    fn example_input(&self, example: Option<u8>) -> String {
+        let folder = data_folder(self.year, "examples");
+        match example {
+            Some(n) => {
+                format!("advent_of_code::template::read_file_part(\"{folder}\", DAY, {n})")
+            }
+            None => format!("advent_of_code::template::read_file(\"{folder}\", DAY)"),
+        }
+    }
+
+    fn example_file(&self, example: Option<u8>) -> String {
+        match example {
+            Some(n) => format!("{}-{n}.txt", self.day),
+            None => format!("{}.txt", self.day),
+        }
+    }
+
+    /// Fill in the placeholders of a template, repeating its example block once per example file.
+    pub fn render(&self, template: &str) -> String {
+        let module = match template
+            .split_once(EXAMPLES_START)
+            .and_then(|(head, rest)| Some((head, rest.split_once(EXAMPLES_END)?)))
+        {
+            Some((head, (block, tail))) => {
+                let blocks: String = self
+                    .examples
+                    .iter()
+                    .map(|example| {
+                        block
+                            .replace(
+                                "%EXAMPLE_SUFFIX%",
+                                &example.map_or_else(String::new, |n| format!("_{n}")),
+                            )
+                            .replace("%EXAMPLE_INPUT%", &self.example_input(*example))
+                            .replace("%EXAMPLE_FILE%", &self.example_file(*example))
+                    })
+                    .collect();
+                format!("{head}{blocks}{tail}")
+            }
+            None => template.to_string(),
+        };
+
+        let day_number = self.day.into_inner().to_string();
+        let example_count = self.examples.iter().flatten().count();
+
+        module
+            .replace("%DAY_NUMBER%", &day_number)
+            .replace("%DAY%", &self.day.to_string())
+            .replace(
+                "%YEAR%",
+                &self
+                    .year
+                    .or_else(Year::from_env)
+                    .map_or_else(String::new, |year| year.to_string()),
+            )
+            .replace(
+                "%YEAR_ARGUMENT%",
+                &self
+                    .year
+                    .map_or_else(String::new, |year| format!(", year = {year}")),
+            )
+            .replace("%EXAMPLES_FOLDER%", &data_folder(self.year, "examples"))
+            .replace("%EXAMPLE_COUNT%", &example_count.to_string())
+            .replace(
+                "%PUZZLE_TITLE%",
+                self.title
+                    .as_deref()
+                    .unwrap_or(&format!("Day {day_number}")),
+            )
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+    }
+}
+
+/// Reads the title from a downloaded puzzle description, e.g. `## --- Day 5: Alchemical Reduction ---`.
+fn puzzle_title(puzzle: &str) -> Option<String> {
+    let heading = puzzle.lines().find(|line| line.starts_with("## "))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim().to_string())
+}
+
+/// Finds the numbers of the example files `NN-k.txt` of a day, in ascending order.
+fn find_examples(folder: &str, day: Day) -> Vec<u8> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return vec![];
+    };
+
+    let prefix = format!("{day}-");
+    let mut examples: Vec<u8> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            name.strip_prefix(&prefix)?
+                .strip_suffix(".txt")?
+                .parse()
+                .ok()
+        })
+        .collect();
+
+    examples.sort_unstable();
+    examples
+}
+
+fn read_template(name: Option<&str>) -> Result<String, std::io::Error> {
+    let path = format!("{TEMPLATES_FOLDER}/{}.txt", name.unwrap_or("default"));
+    match fs::read_to_string(&path) {
+        Err(e) if e.kind() == ErrorKind::NotFound && name.is_none() => Ok(MODULE_TEMPLATE.into()),
+        Err(e) => Err(std::io::Error::new(e.kind(), format!("\"{path}\": {e}"))),
+        template => template,
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file, keeping existing contents such as a downloaded input.
+fn create_empty_file(path: &str, kind: &str) {
+    match safe_create_file(path) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{path}\"");
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing {kind} file \"{path}\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to create {kind} file: {e}");
+            process::exit(1);
+        }
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(year: Option<Year>, day: Day, template: Option<&str>, answer_type: Option<&str>) {
+    let input_folder = format!("data/{}", data_folder(year, "inputs"));
+    let example_folder = format!("data/{}", data_folder(year, "examples"));
+    let puzzle_path = format!("data/{}/{day}.md", data_folder(year, "puzzles"));
+    let input_path = format!("{input_folder}/{day}.txt");
+    let example_path = format!("{example_folder}/{day}.txt");
+    let module_path = format!("src/bin/{}.rs", bin_name(year, day));
+
+    if let Err(e) = fs::create_dir_all(&input_folder).and(fs::create_dir_all(&example_folder)) {
+        eprintln!("Failed to create data folders: {e}");
+        process::exit(1);
+    }
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let examples = find_examples(&example_folder, day);
+
+    let context = TemplateContext {
+        year,
+        day,
+        title: fs::read_to_string(puzzle_path)
+            .ok()
+            .and_then(|puzzle| puzzle_title(&puzzle)),
+        answer_type: answer_type.unwrap_or("u32").into(),
+        examples: if examples.is_empty() {
+            vec![None]
+        } else {
+            examples.into_iter().map(Some).collect()
+        },
+    };
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -34,11 +201,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(context.render(&template).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -48,26 +211,69 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    create_empty_file(&input_path, "input");
+
+    if context.examples == [None] {
+        create_empty_file(&example_path, "example");
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    println!("---");
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, TemplateContext, MODULE_TEMPLATE};
+    use crate::{day, year};
+
+    fn get_context(examples: Vec<Option<u8>>) -> TemplateContext {
+        TemplateContext {
+            year: None,
+            day: day!(5),
+            title: None,
+            answer_type: "u64".into(),
+            examples,
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn renders_default_template() {
+        let module = get_context(vec![None]).render(MODULE_TEMPLATE);
+        assert!(module.starts_with("advent_of_code::solution!(5);"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Result<u64> {"));
+        assert!(module.contains("advent_of_code::example_tests!();"));
+        assert!(!module.contains('%'));
+    }
+
+    #[test]
+    fn renders_one_block_per_example() {
+        let template = "%FOR_EACH_EXAMPLE%\n\
+            fn test_part_one%EXAMPLE_SUFFIX%() { check(\"%EXAMPLE_FILE%\", %EXAMPLE_INPUT%); }\n\
+            %END_FOR_EACH_EXAMPLE%\n";
+        let mut context = get_context(vec![Some(1), Some(2)]);
+        context.year = Some(year!(2019));
+        let module = context.render(template);
+        assert!(module.contains("fn test_part_one_2() { check(\"05-2.txt\", "));
+        assert!(module.contains("read_file_part(\"2019/examples\", DAY, 1)"));
+        assert_eq!(module.matches("fn test_part_one").count(), 2);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let mut context = get_context(vec![Some(1), Some(2), Some(3)]);
+        context.year = Some(year!(2019));
+        context.title = Some("Alchemical Reduction".into());
+        let module = context.render("%YEAR%/%DAY%/%DAY_NUMBER%: %PUZZLE_TITLE% (%EXAMPLE_COUNT%)");
+        assert_eq!(module, "2019/05/5: Alchemical Reduction (3)");
+    }
+
+    #[test]
+    fn reads_puzzle_titles() {
+        let puzzle = "## --- Day 5: Alchemical Reduction ---\n\nYou've managed to...";
+        assert_eq!(puzzle_title(puzzle), Some("Alchemical Reduction".into()));
+        assert_eq!(puzzle_title("no heading"), None);
+    }
 }
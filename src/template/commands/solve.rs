@@ -1,9 +1,18 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{bin_name, Day, Input, Year};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Arguments that are passed on to the solution binary.
+pub struct SolutionArgs {
+    pub json: bool,
+    pub submit_part: Option<u8>,
+    /// `key=value` overrides of the day's parameters.
+    pub params: Vec<String>,
+    pub input: Input,
+}
+
+pub fn handle(year: Option<Year>, day: Day, release: bool, dhat: bool, args: &SolutionArgs) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(year, day)];
 
     if dhat {
         cmd_args.extend([
@@ -18,7 +27,18 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if args.json {
+        cmd_args.push("--json".to_string());
+    }
+
+    for param in &args.params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
+    cmd_args.extend(args.input.to_args());
+
+    if let Some(submit_part) = args.submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
@@ -30,5 +50,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // propagate failures, e.g. answers that do not match the recorded ones.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,16 +1,65 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::compare::Comparison;
+use crate::template::readme_benchmarks::TableConfig;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, MultiRunOptions, Solution, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Compares fresh timings against stored ones when set.
+pub struct CompareOptions {
+    /// Path of a timings snapshot to compare against, defaults to the stored timings.
+    pub baseline: Option<String>,
+    /// Relative change in percent above which a part is flagged.
+    pub threshold: f64,
+}
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<CompareOptions>,
+    options: MultiRunOptions,
+) {
+    if options.jobs > 1 {
+        eprintln!(
+            "Warning: benching {} days in parallel, timings are disturbed by the other days.\n",
+            options.jobs
+        );
+    }
+
+    // fail before benching if the table cannot be stored.
+    let table_config = if store {
+        TableConfig::from_env().unwrap_or_else(|e| {
+            eprintln!("Invalid benchmark table configuration: {e}");
+            process::exit(1);
+        })
+    } else {
+        TableConfig::default()
+    };
+
+    let stored_timings = Timings::read_from_file(year);
+
+    let baseline = compare.as_ref().map(|options| {
+        let Some(path) = &options.baseline else {
+            return stored_timings.clone();
+        };
+        Timings::read_from_path(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read baseline timings: {e}");
+            process::exit(1);
+        })
+    });
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if let Some(baseline) = &baseline {
+                // when comparing, bench the days that are present in the baseline.
+                baseline.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,14 +70,25 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let run = run_multi(solutions, year, &days_to_run, true, options);
+    let has_failures = run.has_failures();
+    let timings = run.timings.unwrap();
+
+    let mut has_regressions = false;
+
+    if let (Some(baseline), Some(options)) = (&baseline, &compare) {
+        let comparison = Comparison::new(baseline, &timings, options.threshold);
+        println!();
+        comparison.print();
+        has_regressions = comparison.regressions().next().is_some();
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = stored_timings.merge(&timings, table_config.history);
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings, &table_config) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +97,13 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_failures {
+        eprintln!("\nOne or more days timed out or panicked.");
+        process::exit(1);
+    }
+
+    if has_regressions {
+        process::exit(1);
+    }
 }
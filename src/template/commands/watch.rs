@@ -0,0 +1,246 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::commands::solve::SolutionArgs;
+use crate::template::record::{PartRecord, PARSE_PHASE};
+use crate::template::runner::{format_duration, format_verdict};
+use crate::template::{bin_name, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// The modification times of the files a day depends on.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Re-runs a day's solution and tests whenever its module, `src/util` or one of its data files
+/// changes. Runs until it is interrupted.
+pub fn handle(year: Option<Year>, day: Day, release: bool, args: &SolutionArgs) {
+    if args.submit_part.is_some() {
+        eprintln!("`--watch` can not be combined with `--submit`.");
+        process::exit(1);
+    }
+
+    let mut snapshot = watched_files(year, day);
+    let mut previous: Vec<PartRecord> = vec![];
+
+    loop {
+        print!("{ANSI_CLEAR}");
+        println!("{ANSI_ITALIC}Watching day {day}, press Ctrl-C to stop.{ANSI_RESET}\n");
+
+        if let Some(records) = run_solution(year, day, release, args) {
+            print_records(&records, &previous);
+            previous = records;
+        }
+        println!();
+        run_tests(year, day);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = watched_files(year, day);
+            if next != snapshot {
+                snapshot = next;
+                break;
+            }
+        }
+    }
+}
+
+/// Runs the solution with `--json` and collects its records.
+/// Returns `None` if it did not build or did not produce any records.
+fn run_solution(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    args: &SolutionArgs,
+) -> Option<Vec<PartRecord>> {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        bin_name(year, day),
+    ];
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+    cmd_args.extend(["--".to_string(), "--json".to_string()]);
+    for param in &args.params {
+        cmd_args.extend(["--param".to_string(), param.clone()]);
+    }
+    cmd_args.extend(args.input.to_args());
+
+    let output = match Command::new("cargo")
+        .args(&cmd_args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            return None;
+        }
+    };
+
+    let mut records = vec![];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match line.parse::<PartRecord>() {
+            Ok(record) => records.push(record),
+            // anything the solution printed itself.
+            Err(_) => println!("{line}"),
+        }
+    }
+
+    (!records.is_empty()).then_some(records)
+}
+
+fn run_tests(year: Option<Year>, day: Day) {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &bin_name(year, day)])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if let Err(e) = status {
+        eprintln!("Failed to run cargo: {e}");
+    }
+}
+
+fn print_records(records: &[PartRecord], previous: &[PartRecord]) {
+    for record in records {
+        let before = previous.iter().find(|r| r.part == record.part);
+        let change = before
+            .map(|before| describe_change(before, record))
+            .unwrap_or_default();
+        let label = match record.part {
+            PARSE_PHASE => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+        let duration = format_duration(&record.time);
+        let verdict = format_verdict(record.verdict, None);
+
+        match (&record.answer, &record.error) {
+            (Some(answer), _) if answer.contains('\n') => {
+                println!("{label}: ▼{verdict}{duration}{change}\n{answer}");
+            }
+            (Some(answer), _) => {
+                println!("{label}: {ANSI_BOLD}{answer}{ANSI_RESET}{verdict}{duration}{change}");
+            }
+            (None, Some(error)) => {
+                println!("{label}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}{duration}{change}");
+            }
+            (None, None) => println!("{label}:{duration}{change}"),
+        }
+    }
+    let _ = stdout().flush();
+}
+
+/// Describes how the answer of a part changed since the previous run, e.g. ` (was 42)`.
+fn describe_change(before: &PartRecord, after: &PartRecord) -> String {
+    if before.answer == after.answer && before.error == after.error {
+        return String::new();
+    }
+
+    match (&before.answer, &before.error) {
+        (Some(answer), _) if !answer.contains('\n') => {
+            format!(" {ANSI_ITALIC}(was {answer}){ANSI_RESET}")
+        }
+        (None, Some(error)) => format!(" {ANSI_ITALIC}(was ✖ {error}){ANSI_RESET}"),
+        _ => format!(" {ANSI_ITALIC}(changed){ANSI_RESET}"),
+    }
+}
+
+/// The day's module, everything in `src/util` and the day's files in `data`, e.g.
+/// `data/inputs/01.txt`, `data/examples/01-2.txt` and the example manifest `data/examples/01.json`.
+fn watched_files(year: Option<Year>, day: Day) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    let module = PathBuf::from(format!("src/bin/{}.rs", bin_name(year, day)));
+    add_file(&mut snapshot, &module);
+    add_dir(&mut snapshot, Path::new("src/util"), true, &|_| true);
+
+    let data = match year {
+        Some(year) => PathBuf::from("data").join(year.to_string()),
+        None => PathBuf::from("data"),
+    };
+    // `data/<folder>/`, but not the folders of other years in `data/<year>/<folder>/`.
+    for folder in fs::read_dir(data).into_iter().flatten().flatten() {
+        add_dir(&mut snapshot, &folder.path(), false, &|name| {
+            is_day_file(name, day)
+        });
+    }
+
+    snapshot
+}
+
+fn add_file(snapshot: &mut Snapshot, path: &Path) {
+    if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+/// Adds the files of a directory whose name matches, and those of its subdirectories if `recursive`.
+fn add_dir(snapshot: &mut Snapshot, dir: &Path, recursive: bool, matches: &dyn Fn(&str) -> bool) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if recursive {
+                add_dir(snapshot, &path, recursive, matches);
+            }
+        } else if matches(&entry.file_name().to_string_lossy()) {
+            add_file(snapshot, &path);
+        }
+    }
+}
+
+/// Whether a data file belongs to `day`, e.g. `01.txt`, `01-2.txt` or `01.json` for day 1.
+fn is_day_file(name: &str, day: Day) -> bool {
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+    let day = day.to_string();
+    let is_day = stem == day || stem.strip_prefix(&day).is_some_and(|s| s.starts_with('-'));
+    is_day && (extension == "txt" || extension == "json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_change, is_day_file};
+    use crate::day;
+    use crate::template::record::{Measurement, PartRecord};
+    use crate::template::ANSI_ITALIC;
+
+    #[test]
+    fn matches_day_files() {
+        assert!(is_day_file("01.txt", day!(1)));
+        assert!(is_day_file("01-2.txt", day!(1)));
+        assert!(is_day_file("01.json", day!(1)));
+        assert!(!is_day_file("010.txt", day!(1)));
+        assert!(!is_day_file("01.md", day!(1)));
+        assert!(!is_day_file("11.txt", day!(1)));
+    }
+
+    #[test]
+    fn describes_changed_answers() {
+        let record = |answer: &str| {
+            PartRecord::new(
+                day!(1),
+                1,
+                Some(&answer),
+                Measurement::single(Default::default()),
+            )
+        };
+        assert_eq!(describe_change(&record("42"), &record("42")), "");
+        assert!(describe_change(&record("41"), &record("42"))
+            .starts_with(&format!(" {ANSI_ITALIC}(was 41)")));
+        assert!(describe_change(&record("a\nb"), &record("42")).contains("(changed)"));
+    }
+}
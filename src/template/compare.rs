@@ -0,0 +1,215 @@
+/// Module that compares fresh benchmark results against a stored baseline.
+use std::time::Duration;
+
+use crate::template::record::{Measurement, PARSE_PHASE};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// The change in median execution time of a single part, or of the parse phase.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub baseline: Option<Duration>,
+    pub current: Option<Duration>,
+}
+
+impl Delta {
+    /// Relative change from baseline to current run in percent, if both were measured.
+    pub fn change(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        let (baseline, current) = (
+            self.baseline?.as_nanos() as f64,
+            self.current?.as_nanos() as f64,
+        );
+        if baseline == 0.0 {
+            return None;
+        }
+        Some((current - baseline) * 100.0 / baseline)
+    }
+}
+
+/// A per-part comparison of two sets of timings.
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub deltas: Vec<Delta>,
+    /// Changes above this percentage are flagged.
+    pub threshold: f64,
+}
+
+fn part_stats(timing: &Timing, part: u8) -> Option<Measurement> {
+    match part {
+        PARSE_PHASE => timing.parse_stats,
+        1 => timing.part_1_stats,
+        _ => timing.part_2_stats,
+    }
+}
+
+impl Comparison {
+    /// Compare every part of `current` against the same part in `baseline`.
+    pub fn new(baseline: &Timings, current: &Timings, threshold: f64) -> Self {
+        let deltas = current
+            .data
+            .iter()
+            .flat_map(|timing| {
+                let stored = baseline.data.iter().find(|t| t.day == timing.day);
+
+                [PARSE_PHASE, 1, 2].into_iter().map(move |part| Delta {
+                    day: timing.day,
+                    part,
+                    baseline: stored.and_then(|t| part_stats(t, part)).map(|s| s.median),
+                    current: part_stats(timing, part).map(|s| s.median),
+                })
+            })
+            .filter(|delta| delta.baseline.is_some() || delta.current.is_some())
+            .collect();
+
+        Self { deltas, threshold }
+    }
+
+    /// Parts that got slower by more than the threshold.
+    pub fn regressions(&self) -> impl Iterator<Item = &Delta> {
+        self.deltas
+            .iter()
+            .filter(|d| d.change().is_some_and(|c| c > self.threshold))
+    }
+
+    fn verdict(&self, delta: &Delta) -> &'static str {
+        match delta.change() {
+            Some(c) if c > self.threshold => "▲ regression",
+            Some(c) if c < -self.threshold => "▼ improvement",
+            Some(_) => "",
+            None => "? not comparable",
+        }
+    }
+
+    /// Render the comparison as a plain text table.
+    pub fn to_table(&self) -> String {
+        let format = |d: Option<Duration>| d.map_or_else(|| "-".into(), |d| format!("{d:.1?}"));
+
+        let mut lines = vec![format!(
+            "{:<5} {:<6} {:>12} {:>12} {:>9}",
+            "Day", "Part", "Baseline", "Current", "Delta"
+        )];
+
+        for delta in &self.deltas {
+            let change = delta
+                .change()
+                .map_or_else(|| "-".into(), |c| format!("{c:+.1}%"));
+
+            lines.push(
+                format!(
+                    "{:<5} {:<6} {:>12} {:>12} {:>9} {}",
+                    delta.day.to_string(),
+                    if delta.part == PARSE_PHASE {
+                        "parse".into()
+                    } else {
+                        delta.part.to_string()
+                    },
+                    format(delta.baseline),
+                    format(delta.current),
+                    change,
+                    self.verdict(delta)
+                )
+                .trim_end()
+                .to_string(),
+            );
+        }
+
+        lines.join("\n")
+    }
+
+    /// Print the comparison table and a summary line.
+    pub fn print(&self) {
+        println!(
+            "{ANSI_BOLD}Comparison (threshold: {}%){ANSI_RESET}",
+            self.threshold
+        );
+        println!("{}", self.to_table());
+
+        let regressions = self.regressions().count();
+        if regressions > 0 {
+            println!("\n{ANSI_BOLD}{regressions} part(s) regressed.{ANSI_RESET}");
+        } else {
+            println!("\nNo regressions.");
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Comparison;
+    use crate::{
+        day,
+        template::{
+            record::Measurement,
+            timings::{Timing, Timings},
+            Day,
+        },
+    };
+
+    fn timing(day: u8, part_1: Option<u64>, part_2: Option<u64>) -> Timing {
+        let stats =
+            |nanos: Option<u64>| nanos.map(|n| Measurement::single(Duration::from_nanos(n)));
+        Timing {
+            day: Day::new(day).unwrap(),
+            parse: None,
+            part_1: part_1.map(|n| format!("{n}ns")),
+            part_2: part_2.map(|n| format!("{n}ns")),
+            parse_stats: None,
+            part_1_stats: stats(part_1),
+            part_2_stats: stats(part_2),
+            total_nanos: 0_f64,
+            history: vec![],
+        }
+    }
+
+    fn get_comparison() -> Comparison {
+        let baseline = Timings {
+            data: vec![timing(1, Some(100), Some(200)), timing(2, Some(100), None)],
+        };
+        let current = Timings {
+            data: vec![
+                timing(1, Some(105), Some(100)),
+                timing(2, Some(150), Some(10)),
+                timing(3, Some(10), None),
+            ],
+        };
+        Comparison::new(&baseline, &current, 10.0)
+    }
+
+    #[test]
+    fn computes_deltas() {
+        let comparison = get_comparison();
+        assert_eq!(comparison.deltas.len(), 5);
+        assert_eq!(comparison.deltas[0].change(), Some(5.0));
+        assert_eq!(comparison.deltas[1].change(), Some(-50.0));
+        assert_eq!(comparison.deltas[3].change(), None);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let comparison = get_comparison();
+        let regressions: Vec<_> = comparison.regressions().collect();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].day, day!(2));
+        assert_eq!(regressions[0].part, 1);
+    }
+
+    #[test]
+    fn formats_table() {
+        let table = get_comparison().to_table();
+        let expected = [
+            "Day   Part       Baseline      Current     Delta",
+            "01    1           100.0ns      105.0ns     +5.0%",
+            "01    2           200.0ns      100.0ns    -50.0% ▼ improvement",
+            "02    1           100.0ns      150.0ns    +50.0% ▲ regression",
+            "02    2                 -       10.0ns         - ? not comparable",
+            "03    1                 -       10.0ns         - ? not comparable",
+        ]
+        .join("\n");
+        assert_eq!(table, expected);
+    }
+}
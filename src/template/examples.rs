@@ -0,0 +1,465 @@
+/// Module that extracts examples and their expected answers from puzzle descriptions,
+/// and checks solutions against them.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::record::{PartStatus, PARSE_PHASE};
+use crate::template::runner::RunOptions;
+use crate::template::{data_folder, Day, IntoAnswer, Solution, Year};
+
+/// An example input along with the answer the puzzle expects for one part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Name of the example file in `data/examples`, e.g. `05-1.txt`.
+    pub file: String,
+    pub part: u8,
+    /// Parameters the example uses in place of the real input's, e.g. a smaller grid size.
+    pub params: BTreeMap<String, String>,
+    pub expected: String,
+}
+
+/// The examples of a single day, stored in `data/examples/<day>.json`.
+///
+/// Solutions that call [`example_tests!`](crate::example_tests) get one test per example.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleManifest {
+    pub examples: Vec<Example>,
+}
+
+fn get_manifest_path(year: Option<Year>, day: Day) -> String {
+    format!("data/{}/{day}.json", data_folder(year, "examples"))
+}
+
+impl ExampleManifest {
+    /// Read the manifest of a day. If not present, returns an empty manifest.
+    pub fn read(year: Option<Year>, day: Day) -> Self {
+        match fs::read_to_string(get_manifest_path(year, day)) {
+            Ok(s) => ExampleManifest::try_from(s).unwrap_or_else(|e| {
+                eprintln!("{}: {e}", get_manifest_path(year, day));
+                ExampleManifest::default()
+            }),
+            Err(_) => ExampleManifest::default(),
+        }
+    }
+
+    /// Write the manifest of a day to disk.
+    pub fn store(&self, year: Option<Year>, day: Day) -> Result<(), io::Error> {
+        let mut file = fs::File::create(get_manifest_path(year, day))?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// The first example of `file` for `part`.
+    pub fn get(&self, file: &str, part: u8) -> Option<&Example> {
+        self.examples
+            .iter()
+            .find(|e| e.file == file && e.part == part)
+    }
+
+    pub fn expected(&self, file: &str, part: u8) -> Option<&str> {
+        self.get(file, part).map(|e| e.expected.as_str())
+    }
+}
+
+/// Run a part against an example file and compare its result to the expected answer in the
+/// manifest. Fails if the manifest has no answer for this example and part yet.
+#[track_caller]
+pub fn check<R: IntoAnswer>(
+    year: Option<Year>,
+    day: Day,
+    file: &str,
+    part: u8,
+    func: impl Fn(&str) -> R,
+) {
+    let manifest = ExampleManifest::read(year, day);
+    let Some(expected) = manifest.expected(file, part) else {
+        panic!(
+            "no expected value for part {part} of {file} in {}",
+            get_manifest_path(year, day)
+        );
+    };
+
+    let path = format!("data/{}/{file}", data_folder(year, "examples"));
+    let input = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not open example file \"{path}\": {e}"));
+
+    let result = func(&input)
+        .into_answer()
+        .unwrap_or_else(|e| panic!("example \"{file}\", part {part} failed: {e:#}"))
+        .to_string();
+    assert_eq!(
+        result, expected,
+        "unexpected answer for example \"{file}\", part {part}"
+    );
+}
+
+/// Like [`check`], but runs the `index`-th example of the manifest through the registry entry of
+/// a day, so that days with a parse phase or parameters can be checked as well. Used by the
+/// tests of [`example_tests!`](crate::example_tests).
+#[track_caller]
+pub fn check_solution(solution: &Solution, index: usize) {
+    let manifest = ExampleManifest::read(solution.year, solution.day);
+    let Some(example) = manifest.examples.get(index) else {
+        panic!(
+            "no example {index} in {}",
+            get_manifest_path(solution.year, solution.day)
+        );
+    };
+    let Example {
+        file,
+        part,
+        params,
+        expected,
+    } = example;
+    let part = *part;
+
+    let path = format!("data/{}/{file}", data_folder(solution.year, "examples"));
+    let input = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not open example file \"{path}\": {e}"));
+
+    let options = RunOptions {
+        is_custom_input: true,
+        ..RunOptions::default()
+    };
+    let records = (solution.run)(&input, &[part], params, options);
+    let parse = records
+        .iter()
+        .find(|r| r.part == PARSE_PHASE && r.status != PartStatus::Solved);
+
+    // parts that panicked or got invalid parameters have no record.
+    let answer = match (parse, records.iter().find(|r| r.part == part)) {
+        (Some(parse), _) => Err(parse.error.as_deref()),
+        (None, Some(record)) => record.answer.as_ref().ok_or(record.error.as_deref()),
+        (None, None) => Err(Some("did not run, see the output above")),
+    }
+    .unwrap_or_else(|error| {
+        panic!(
+            "example \"{file}\", part {part} failed: {}",
+            error.unwrap_or("no answer")
+        )
+    });
+
+    assert_eq!(
+        answer, expected,
+        "unexpected answer for example \"{file}\", part {part}"
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Examples found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    /// The contents of the example files, in order.
+    pub inputs: Vec<String>,
+    pub manifest: ExampleManifest,
+}
+
+/// The name of the `n`-th example file of a day, starting at 1.
+pub fn example_file(day: Day, n: usize) -> String {
+    format!("{day}-{n}.txt")
+}
+
+/// Finds the example inputs and expected answers in a puzzle description, as written by `read`.
+///
+/// Code blocks that are introduced by a paragraph mentioning an example are treated as example
+/// inputs. The last emphasized code (e.g. *`42`*) after an example and before the next one is
+/// taken as its expected answer, separately for both parts.
+pub fn extract(day: Day, markdown: &str) -> Extracted {
+    let mut blocks: Vec<(bool, String)> = vec![];
+    // (number of preceding blocks, part, answer) in order of appearance.
+    let mut answers: Vec<(usize, u8, String)> = vec![];
+
+    let mut part = 1;
+    // the most recent paragraph, which introduces the next block.
+    let mut paragraph = String::new();
+    let mut paragraph_ended = false;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if let Some(content) = &mut block {
+            if line == "```" {
+                let is_example = paragraph.to_lowercase().contains("example");
+                blocks.push((is_example, std::mem::take(content)));
+                block = None;
+            } else {
+                content.push_str(line);
+                content.push('\n');
+            }
+            continue;
+        }
+
+        if line == "```" {
+            block = Some(String::new());
+        } else if line.starts_with("## ") && line.contains("Part Two") {
+            part = 2;
+        } else if line.is_empty() {
+            paragraph_ended = true;
+        } else {
+            if paragraph_ended {
+                paragraph.clear();
+                paragraph_ended = false;
+            }
+            paragraph.push_str(line);
+            paragraph.push('\n');
+            answers.extend(
+                emphasized_code(line).map(|answer| (blocks.len(), part, answer.to_string())),
+            );
+        }
+    }
+
+    // fall back to the first block for puzzles that do not call their example an example.
+    if !blocks.iter().any(|(is_example, _)| *is_example) {
+        if let Some(first) = blocks.first_mut() {
+            first.0 = true;
+        }
+    }
+
+    let mut inputs: Vec<String> = vec![];
+    let mut files: HashMap<usize, String> = HashMap::new();
+
+    for (index, (is_example, content)) in blocks.into_iter().enumerate() {
+        if is_example {
+            inputs.push(content);
+            files.insert(index, example_file(day, inputs.len()));
+        }
+    }
+
+    let mut examples: Vec<Example> = vec![];
+
+    for (preceding, part, answer) in answers {
+        // answers belong to the most recent example.
+        let Some(file) = (0..preceding).rev().find_map(|index| files.get(&index)) else {
+            continue;
+        };
+        // later answers replace earlier, intermediate ones.
+        examples.retain(|e| !(&e.file == file && e.part == part));
+        examples.push(Example {
+            file: file.clone(),
+            part,
+            params: BTreeMap::new(),
+            expected: answer,
+        });
+    }
+
+    // sort by part, then by the numeric order of the file names.
+    examples.sort_by(|a, b| (a.part, a.file.len(), &a.file).cmp(&(b.part, b.file.len(), &b.file)));
+
+    Extracted {
+        inputs,
+        manifest: ExampleManifest { examples },
+    }
+}
+
+/// Iterates over the emphasized code spans of a line, i.e. `` *`x`* ``.
+fn emphasized_code(line: &str) -> impl Iterator<Item = &str> {
+    line.split("*`")
+        .skip(1)
+        .filter_map(|chunk| chunk.split_once("`*").map(|(code, _)| code))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&ExampleManifest> for JsonValue {
+    fn from(value: &ExampleManifest) -> Self {
+        let examples = value
+            .examples
+            .iter()
+            .map(|example| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("file".into(), JsonValue::String(example.file.clone()));
+                map.insert("part".into(), JsonValue::Number(f64::from(example.part)));
+                if !example.params.is_empty() {
+                    let params = example
+                        .params
+                        .iter()
+                        .map(|(k, v)| (k.clone(), JsonValue::String(v.clone())))
+                        .collect();
+                    map.insert("params".into(), JsonValue::Object(params));
+                }
+                map.insert(
+                    "expected".into(),
+                    JsonValue::String(example.expected.clone()),
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("examples".into(), JsonValue::Array(examples));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for ExampleManifest {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to contain key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `examples` to be an array.")?;
+
+        let examples = examples
+            .iter()
+            .map(|example| {
+                let example = example
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("expected examples to be objects.")?;
+
+                let string = |key: &str| {
+                    example
+                        .get(key)
+                        .and_then(|v| v.get::<String>())
+                        .cloned()
+                        .ok_or(format!("expected example.{key} to be a string."))
+                };
+
+                let part = example
+                    .get("part")
+                    .and_then(|v| v.get::<f64>())
+                    .ok_or("expected example.part to be a number.")?;
+
+                let params = match example.get("params") {
+                    Some(params) => params
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or("expected example.params to be an object.")?
+                        .iter()
+                        .map(|(k, v)| {
+                            let v = v
+                                .get::<String>()
+                                .ok_or(format!("expected example.params.{k} to be a string."))?;
+                            Ok((k.clone(), v.clone()))
+                        })
+                        .collect::<Result<_, String>>()?,
+                    None => BTreeMap::new(),
+                };
+
+                Ok(Example {
+                    file: string("file")?,
+                    part: *part as u8,
+                    params,
+                    expected: string("expected")?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(ExampleManifest { examples })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use tinyjson::JsonValue;
+
+    use super::{check, extract, Example, ExampleManifest};
+    use crate::day;
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+The frequency changes.
+
+```
++1
+-1
+```
+
+For example, if the device displays frequency changes of +1, -2:
+
+```
++1
+-2
+```
+
+Starting with a frequency of *`0`*, the resulting frequency is *`-1`*.
+
+Other examples:
+
+```
++1
++1
+```
+
+This results in *`2`*.
+
+What is the resulting frequency?
+
+## --- Part Two ---
+
+For this example, the first frequency reached twice is *`0`*.
+
+What is the first frequency your device reaches twice?";
+
+    fn example(file: &str, part: u8, expected: &str) -> Example {
+        Example {
+            file: file.into(),
+            part,
+            params: BTreeMap::new(),
+            expected: expected.into(),
+        }
+    }
+
+    #[test]
+    fn extracts_example_inputs() {
+        let extracted = extract(day!(1), PUZZLE);
+        assert_eq!(extracted.inputs, vec!["+1\n-2\n", "+1\n+1\n"]);
+    }
+
+    #[test]
+    fn extracts_expected_answers() {
+        let extracted = extract(day!(1), PUZZLE);
+        assert_eq!(
+            extracted.manifest.examples,
+            vec![
+                example("01-1.txt", 1, "-1"),
+                example("01-2.txt", 1, "2"),
+                example("01-2.txt", 2, "0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_block() {
+        let extracted = extract(day!(1), "Given:\n\n```\nabc\n```\n\nYields *`3`*.");
+        assert_eq!(extracted.inputs, vec!["abc\n"]);
+        assert_eq!(
+            extracted.manifest.examples,
+            vec![example("01-1.txt", 1, "3")]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "no expected value for part 1 of 24-missing.txt in data/examples/24.json"
+    )]
+    fn fails_without_expected_value() {
+        check(None, day!(24), "24-missing.txt", 1, |_| Some(0));
+    }
+
+    #[test]
+    fn round_trips_manifest() {
+        let mut with_params = example("01-1.txt", 2, "0");
+        with_params.params.insert("workers".into(), "2".into());
+        let manifest = ExampleManifest {
+            examples: vec![example("01-1.txt", 1, "-1"), with_params],
+        };
+        let json = JsonValue::from(&manifest).stringify().unwrap();
+        assert_eq!(ExampleManifest::try_from(json).unwrap(), manifest);
+        assert_eq!(manifest.expected("01-1.txt", 2), Some("0"));
+        assert_eq!(manifest.expected("01-2.txt", 1), None);
+    }
+}
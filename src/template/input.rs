@@ -0,0 +1,141 @@
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::{data_folder, Day, Year};
+
+/// Where a solution binary reads its input from.
+///
+/// Selected with the `--input <path>` and `--example [k]` arguments, see [`Input::from_args`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Input {
+    /// The puzzle input in `data/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// The example in `data/examples/<day>.txt`, or `<day>-<k>.txt` for the k-th example.
+    Example(Option<u8>),
+    /// Any file, e.g. the puzzle input of a teammate.
+    File(PathBuf),
+    /// Standard input, selected with `--input -`.
+    Stdin,
+}
+
+impl Input {
+    /// Reads the input source from the arguments passed to a solution binary.
+    pub fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        Self::parse(&args)
+    }
+
+    /// Parses the input source from a list of arguments, see [`Input::from_args`].
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let input = args.iter().position(|x| x == "--input");
+        let example = args.iter().position(|x| x == "--example");
+
+        match (input, example) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` can not be combined.".into()),
+            (Some(i), None) => match args.get(i + 1).map(String::as_str) {
+                Some("-") => Ok(Input::Stdin),
+                Some(path) if !path.starts_with("--") => Ok(Input::File(path.into())),
+                _ => Err("expected a path or `-` after `--input`.".into()),
+            },
+            (None, Some(i)) => match args.get(i + 1) {
+                Some(k) if !k.starts_with("--") => {
+                    k.parse().map(|k| Input::Example(Some(k))).map_err(|_| {
+                        format!("expected an example number after `--example`, got `{k}`.")
+                    })
+                }
+                _ => Ok(Input::Example(None)),
+            },
+            (None, None) => Ok(Input::Puzzle),
+        }
+    }
+
+    /// The arguments that select this input source, the inverse of [`Input::parse`].
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Input::Puzzle => vec![],
+            Input::Example(None) => vec!["--example".into()],
+            Input::Example(Some(k)) => vec!["--example".into(), k.to_string()],
+            Input::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            Input::Stdin => vec!["--input".into(), "-".into()],
+        }
+    }
+
+    /// Whether this is the puzzle input, the only input that recorded answers apply to.
+    pub fn is_puzzle(&self) -> bool {
+        *self == Input::Puzzle
+    }
+
+    /// Reads the input of `day`.
+    pub fn read(&self, year: Option<Year>, day: Day) -> Result<String, String> {
+        let path: PathBuf = match self {
+            Input::Puzzle => format!("data/{}/{day}.txt", data_folder(year, "inputs")).into(),
+            Input::Example(None) => {
+                format!("data/{}/{day}.txt", data_folder(year, "examples")).into()
+            }
+            Input::Example(Some(k)) => {
+                format!("data/{}/{day}-{k}.txt", data_folder(year, "examples")).into()
+            }
+            Input::File(path) => path.clone(),
+            Input::Stdin => {
+                let mut input = String::new();
+                return io::stdin()
+                    .read_to_string(&mut input)
+                    .map(|_| input)
+                    .map_err(|e| format!("could not read input from stdin: {e}"));
+            }
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| format!("could not open input file \"{}\": {e}", path.display()))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Input;
+
+    fn parse(args: &[&str]) -> Result<Input, String> {
+        let args: Vec<String> = args.iter().map(|x| x.to_string()).collect();
+        Input::parse(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&["--time"]), Ok(Input::Puzzle));
+        assert_eq!(parse(&["--input", "-"]), Ok(Input::Stdin));
+        assert_eq!(
+            parse(&["--input", "inputs/15.txt", "--time"]),
+            Ok(Input::File("inputs/15.txt".into()))
+        );
+        assert_eq!(parse(&["--example"]), Ok(Input::Example(None)));
+        assert_eq!(parse(&["--example", "--time"]), Ok(Input::Example(None)));
+        assert_eq!(parse(&["--example", "3"]), Ok(Input::Example(Some(3))));
+    }
+
+    #[test]
+    fn rejects_invalid_input_sources() {
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--input", "--time"]).is_err());
+        assert!(parse(&["--input", "-", "--example"]).is_err());
+        assert!(parse(&["--example", "abc"]).is_err());
+        assert!(parse(&["--example", "-1"]).is_err());
+        assert!(parse(&["--example", "300"]).is_err());
+    }
+
+    #[test]
+    fn converts_to_args() {
+        for input in [
+            Input::Puzzle,
+            Input::Stdin,
+            Input::File("inputs/15.txt".into()),
+            Input::Example(None),
+            Input::Example(Some(3)),
+        ] {
+            assert_eq!(Input::parse(&input.to_args()), Ok(input));
+        }
+    }
+}
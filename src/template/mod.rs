@@ -1,15 +1,34 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod output;
+pub mod record;
 pub mod runner;
+pub mod submissions;
 
 pub use day::*;
+pub use input::*;
+pub use outcome::*;
+pub use params::*;
+pub use registry::*;
+pub use report::ReportFormat;
+pub use run_multi::{DayOutcome, DaySummary, MultiRunOptions};
+pub use year::*;
 
+mod compare;
 mod day;
+mod input;
+mod outcome;
+mod params;
 mod readme_benchmarks;
+mod registry;
+mod report;
 mod run_multi;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -18,12 +37,17 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = try_read_file(folder, day);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, day: Day) -> std::io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    fs::read_to_string(filepath)
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -36,33 +60,141 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional `parse = <function>` parameter parses the input once, before the parts run. Its
+/// output is handed to both parts by reference and it is timed separately.
+/// The optional `params = <type>` parameter hands a [`Params`] struct to both parts as their second
+/// argument. Its defaults can be overridden with `--param key=value`.
+/// The optional `year = <year>` parameter marks solutions of a year other than the workspace's
+/// default year, which read their files from the year's namespace in `data/`.
+///
+/// Also creates the constant `SOLUTION`, which registers the parts so that the main binary can
+/// run them in-process.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (@year) => {
+        None
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@year $year:expr) => {
+        Some($crate::year!($year))
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+
+    (@params []) => {
+        ()
+    };
+    (@params [$params:ty]) => {
+        $params
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // binds the parameters to a part, if the day has any.
+    (@part [], $func:expr, $params:ident) => {
+        $func
+    };
+    (@part [$p:ty], $func:expr, $params:ident) => {
+        |input| $func(input, $params)
+    };
+
+    // runs the parts, handing them the parsed input if the day has a parse phase.
+    (@run [], $input:expr, $options:expr, $run:expr) => {
+        $run($input)
+    };
+    (@run [$parse:expr], $input:expr, $options:expr, $run:expr) => {{
+        let (parsed, record) = $crate::template::runner::run_parse($parse, $input, DAY, $options);
+        let mut records = vec![record];
+        if let Some(parsed) = parsed {
+            records.extend($run(&parsed));
+        }
+        records
+    }};
+
+    ($day:expr $(, parse = $parse:expr)? $(, params = $params:ty)? $(, year = $year:expr)?) => {
+        $crate::solution!(
+            @impl $day, [$($year)?], [$($parse)?], [$($params)?], [part_one, 1] [part_two, 2]
+        );
+    };
+    ($day:expr, 1 $(, parse = $parse:expr)? $(, params = $params:ty)? $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [$($parse)?], [$($params)?], [part_one, 1]);
+    };
+    ($day:expr, 2 $(, parse = $parse:expr)? $(, params = $params:ty)? $(, year = $year:expr)?) => {
+        $crate::solution!(@impl $day, [$($year)?], [$($parse)?], [$($params)?], [part_two, 2]);
+    };
+
+    (
+        @impl $day:expr, [$($year:expr)?], [$($parse:expr)?], $params:tt,
+        $( [$func:expr, $part:expr] )*
+    ) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The year of the current day, `None` for the workspace's default year.
+        #[allow(dead_code)]
+        const YEAR: Option<$crate::template::Year> = $crate::solution!(@year $($year)?);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// The registry entry for the current day.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            year: YEAR,
+            day: DAY,
+            parts: &[$($part),*],
+            run: |input, parts, overrides, options| {
+                let params = match <$crate::solution!(@params $params) as $crate::template::Params>::with_overrides(overrides) {
+                    Ok(params) => params,
+                    Err(e) => {
+                        eprintln!("Invalid parameters for day {DAY}: {e}");
+                        return vec![];
+                    }
+                };
+                #[allow(unused_variables)]
+                let params = &params;
+
+                $crate::solution!(@run [$($parse)?], input, options, |input| {
+                    let mut records = vec![];
+                    $(
+                        if parts.contains(&$part) {
+                            records.extend($crate::template::runner::run_registered(
+                                $crate::solution!(@part $params, $func, params),
+                                input, YEAR, DAY, $part, options,
+                            ));
+                        }
+                    )*
+                    records
+                })
+            },
+        };
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            #[allow(unused_variables)]
+            let params = &params_from_args::<$crate::solution!(@params $params)>();
+            let input = input_from_args(YEAR, DAY);
+            let records = $crate::solution!(@run [$($parse)?], &input, RunOptions::from_args(), |input| {
+                vec![$(
+                    run_part($crate::solution!(@part $params, $func, params), input, YEAR, DAY, $part),
+                )*]
+            });
+            check_answers(&records);
         }
     };
 }
+
+/// Expands into one test per entry of the day's example manifest, `data/examples/<day>.json`.
+///
+/// Use it inside the `tests` module of a solution. The tests are generated by the build script,
+/// so adding an example to the manifest is enough to test it.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/examples/",
+            env!("CARGO_BIN_NAME"),
+            ".rs"
+        ));
+    };
+}
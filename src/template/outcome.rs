@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Why a solution part did not produce an answer.
+///
+/// Return it from a part, or attach it to an error as context, to tell the runner what happened:
+///
+/// ```
+/// # use advent_of_code::template::Failure;
+/// # use anyhow::{Context, Result};
+/// pub fn part_one(input: &str) -> Result<u32> {
+///     let depth: u32 = input.trim().parse().context(Failure::Parse)?;
+///     Ok(depth * 2)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part has not been implemented yet.
+    NotImplemented,
+    /// The input could not be parsed.
+    Parse,
+    /// The part ran, but did not find an answer.
+    NoAnswer,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Failure::NotImplemented => "not implemented",
+            Failure::Parse => "failed to parse input",
+            Failure::NoAnswer => "no answer found",
+        })
+    }
+}
+
+impl Error for Failure {}
+
+/// The return type of a solution part, either an [`Option`] or a [`Result`].
+pub trait IntoAnswer {
+    type Answer: Display;
+
+    /// Converts the value into the answer of the part. `None` becomes [`Failure::NoAnswer`].
+    fn into_answer(self) -> anyhow::Result<Self::Answer>;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> anyhow::Result<T> {
+        self.ok_or(Failure::NoAnswer.into())
+    }
+}
+
+impl<T: Display, E: Into<anyhow::Error>> IntoAnswer for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> anyhow::Result<T> {
+        self.map_err(Into::into)
+    }
+}
+
+/// Finds the [`Failure`] of an error, if it is or was caused by one.
+pub fn failure(error: &anyhow::Error) -> Option<Failure> {
+    error.downcast_ref::<Failure>().copied()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use anyhow::{anyhow, Context};
+
+    use super::{failure, Failure, IntoAnswer};
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).into_answer().unwrap(), 42);
+        let error = None::<u32>.into_answer().unwrap_err();
+        assert_eq!(failure(&error), Some(Failure::NoAnswer));
+    }
+
+    #[test]
+    fn finds_failures_in_context() {
+        let result: anyhow::Result<u32> = "x".parse::<u32>().context(Failure::Parse);
+        let error = result.into_answer().unwrap_err();
+        assert_eq!(failure(&error), Some(Failure::Parse));
+        assert_eq!(
+            format!("{error:#}"),
+            "failed to parse input: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn finds_failures_below_other_context() {
+        let result: anyhow::Result<u32> = Err(anyhow!("bad line"))
+            .context(Failure::Parse)
+            .context("line 3");
+        assert_eq!(failure(&result.unwrap_err()), Some(Failure::Parse));
+        assert_eq!(failure(&anyhow!("oops")), None);
+    }
+}
@@ -0,0 +1,96 @@
+/// Output of the runner and the solutions, which can be captured per thread so that days that
+/// run in parallel do not interleave their output. Solutions print with
+/// [`advent_of_code::out!`](crate::out) and [`advent_of_code::outln!`](crate::outln) instead of
+/// `print!` and `println!` for their output to be captured as well.
+use std::cell::RefCell;
+use std::fmt::{self, Write as _};
+use std::io::{stdout, Write as _};
+
+thread_local! {
+    static BUFFER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` and returns the output the runner printed on this thread in the meantime,
+/// instead of printing it. Progress indicators are left out.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = BUFFER.with_borrow_mut(|buffer| buffer.replace(String::new()));
+    let result = f();
+    let output = BUFFER.with_borrow_mut(|buffer| std::mem::replace(buffer, previous));
+    (result, output.unwrap_or_default())
+}
+
+/// Whether the output of the current thread is captured, see [`capture`].
+pub fn is_captured() -> bool {
+    BUFFER.with_borrow(Option::is_some)
+}
+
+/// Writes to stdout, or to the buffer of the current thread while its output is captured.
+pub fn write(args: fmt::Arguments, is_progress: bool) {
+    BUFFER.with_borrow_mut(|buffer| match buffer {
+        Some(_) if is_progress => {}
+        Some(buffer) => {
+            let _ = buffer.write_fmt(args);
+        }
+        None => {
+            let mut stdout = stdout();
+            let _ = stdout.write_fmt(args);
+            if is_progress {
+                let _ = stdout.flush();
+            }
+        }
+    });
+}
+
+/// Like `print!`, but can be captured, see [`capture`].
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!($($arg)*), false)
+    };
+}
+
+/// Like `println!`, but can be captured, see [`capture`].
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::template::output::write(format_args!("\n"), false)
+    };
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!("{}\n", format_args!($($arg)*)), false)
+    };
+}
+
+/// Prints a progress indicator, e.g. a part's answer before it is benched.
+/// Left out of captured output.
+macro_rules! progress {
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!($($arg)*), true)
+    };
+}
+
+pub(crate) use crate::{out, outln};
+pub(crate) use progress;
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture, is_captured};
+
+    #[test]
+    fn captures_output() {
+        assert!(!is_captured());
+        let (result, output) = capture(|| {
+            assert!(is_captured());
+            progress!("Part 1: 42");
+            progress!("\r");
+            out!("Part 1: ");
+            outln!("{} (1.0ms)", 42);
+            let (_, inner) = capture(|| outln!("Part 2: 43"));
+            assert_eq!(inner, "Part 2: 43\n");
+            42
+        });
+        assert_eq!(result, 42);
+        assert_eq!(output, "Part 1: 42 (1.0ms)\n");
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::env;
+
+/// `key=value` overrides of a day's parameters.
+pub type Overrides = BTreeMap<String, String>;
+
+/// Constants of a day that differ between the puzzle's examples and the real input, e.g. the
+/// number of workers. The defaults apply to the real input.
+///
+/// Declare them with [`params!`](crate::params) and pass the type to `solution!` as
+/// `params = <type>`, the parts then receive them as their second argument.
+pub trait Params: Default {
+    /// Overrides a single parameter with a value in its string form.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// The defaults with the given overrides applied.
+    fn with_overrides(overrides: &Overrides) -> Result<Self, String> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Days without parameters.
+impl Params for () {
+    fn set(&mut self, key: &str, _: &str) -> Result<(), String> {
+        Err(format!(
+            "unknown parameter `{key}`, this day has no parameters."
+        ))
+    }
+}
+
+/// Parses a `key=value` parameter override.
+pub fn parse_override(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or(format!(
+            "expected a parameter of the form `key=value`, got `{s}`."
+        ))
+}
+
+/// Reads the `--param key=value` arguments passed to a solution binary.
+pub fn overrides_from_args() -> Result<Overrides, String> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .zip(args.iter().skip(1))
+        .filter(|(flag, _)| *flag == "--param")
+        .map(|(_, param)| parse_override(param))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Declares a parameter struct with the defaults for the real input, see [`Params`].
+///
+/// ```
+/// advent_of_code::params! {
+///     pub struct Params {
+///         workers: usize = 5,
+///         base: u32 = 60,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $( $(#[$field_meta])* pub $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $( $field: $default, )*
+                }
+            }
+        }
+
+        impl $crate::template::Params for $name {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value.parse().map_err(|e| {
+                                format!("invalid value `{value}` for parameter `{key}`: {e}")
+                            })?;
+                        }
+                    )*
+                    _ => return Err(format!("unknown parameter `{key}`.")),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_override, Overrides, Params};
+
+    crate::params! {
+        struct TestParams {
+            workers: usize = 5,
+            base: u32 = 60,
+        }
+    }
+
+    fn overrides(pairs: &[(&str, &str)]) -> Overrides {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let params = TestParams::with_overrides(&overrides(&[("workers", "2")])).unwrap();
+        assert_eq!(params.workers, 2);
+        assert_eq!(params.base, 60);
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        assert!(TestParams::with_overrides(&overrides(&[("workers", "two")])).is_err());
+        assert!(TestParams::with_overrides(&overrides(&[("elves", "2")])).is_err());
+        assert!(<()>::with_overrides(&overrides(&[("workers", "2")])).is_err());
+        assert!(<()>::with_overrides(&Overrides::new()).is_ok());
+    }
+
+    #[test]
+    fn parses_overrides() {
+        assert_eq!(
+            parse_override("workers=2"),
+            Ok(("workers".into(), "2".into()))
+        );
+        assert!(parse_override("workers").is_err());
+        assert!(parse_override("=2").is_err());
+    }
+}
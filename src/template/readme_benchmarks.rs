@@ -1,12 +1,21 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, iter, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::record::Measurement;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{bin_name, Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Namespaced years have their own table, enclosed by e.g. `<!--- benchmarking table 2019 --->`.
+fn get_marker(year: Option<Year>) -> String {
+    match year {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -26,12 +35,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +61,359 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// A column of the benchmark table, next to the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    /// The number of samples of both parts.
+    Samples,
+    /// The speedup of a day versus its last stored run.
+    Speedup,
+    /// The share of a day in the total time.
+    Percent,
+    /// A sparkline of the totals of the last stored runs of a day.
+    History,
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Samples => "Samples",
+            Column::Speedup => "Speedup",
+            Column::Percent => "% of total",
+            Column::History => "History",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "samples" => Ok(Column::Samples),
+            "speedup" => Ok(Column::Speedup),
+            "percent" => Ok(Column::Percent),
+            "history" => Ok(Column::History),
+            s => Err(format!("unknown benchmark column `{s}`.")),
+        }
+    }
+}
+
+/// The statistic that the time columns show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Statistic {
+    Median,
+    Mean,
+}
+
+impl FromStr for Statistic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "median" => Ok(Statistic::Median),
+            "mean" => Ok(Statistic::Mean),
+            s => Err(format!("unknown benchmark statistic `{s}`.")),
+        }
+    }
+}
+
+/// Layout of the benchmark table, configured with the `AOC_BENCHMARK_*` variables
+/// in `.cargo/config.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableConfig {
+    pub columns: Vec<Column>,
+    pub statistic: Statistic,
+    /// The number of stored runs that are kept per day in `timings.json`.
+    pub history: usize,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Part1, Column::Part2],
+            statistic: Statistic::Median,
+            history: 10,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Reads the configuration from `AOC_BENCHMARK_COLUMNS` (a comma-separated list of columns),
+    /// `AOC_BENCHMARK_STATISTIC` and `AOC_BENCHMARK_HISTORY`. Unset variables keep their default.
+    pub fn from_env() -> Result<Self, String> {
+        let var = |key: &str| env::var(key).ok().filter(|v| !v.trim().is_empty());
+        let mut config = Self::default();
+
+        if let Some(columns) = var("AOC_BENCHMARK_COLUMNS") {
+            config.columns = columns
+                .split(',')
+                .map(|column| column.trim().parse())
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(statistic) = var("AOC_BENCHMARK_STATISTIC") {
+            config.statistic = statistic.trim().parse()?;
+        }
+        if let Some(history) = var("AOC_BENCHMARK_HISTORY") {
+            config.history = history
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid benchmark history `{history}`."))?;
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The time of a part of a day, or of its parse phase.
+/// Timings stored by older versions only have the formatted median.
+fn part_time(timing: &Timing, column: Column, statistic: Statistic) -> Option<String> {
+    let (formatted, stats) = match column {
+        Column::Parse => (&timing.parse, timing.parse_stats),
+        Column::Part1 => (&timing.part_1, timing.part_1_stats),
+        Column::Part2 => (&timing.part_2, timing.part_2_stats),
+        _ => return None,
+    };
+    match (statistic, stats) {
+        (Statistic::Mean, Some(stats)) => Some(format!("{:.1?}", stats.mean)),
+        _ => formatted.clone(),
+    }
+}
+
+fn part_nanos(timing: &Timing, column: Column, statistic: Statistic) -> Option<Duration> {
+    let stats = match column {
+        Column::Parse => timing.parse_stats,
+        Column::Part1 => timing.part_1_stats,
+        Column::Part2 => timing.part_2_stats,
+        _ => None,
+    }?;
+    Some(match statistic {
+        Statistic::Median => stats.median,
+        Statistic::Mean => stats.mean,
+    })
+}
+
+/// The total time of a day. Timings stored by older versions fall back to their median total.
+fn day_nanos(timing: &Timing, statistic: Statistic) -> f64 {
+    let parts = [Column::Parse, Column::Part1, Column::Part2];
+    match statistic {
+        Statistic::Median => timing.total_nanos,
+        Statistic::Mean if timing.part_1_stats.is_none() => timing.total_nanos,
+        Statistic::Mean => {
+            let mean: Duration = parts
+                .into_iter()
+                .filter_map(|part| part_nanos(timing, part, statistic))
+                .sum();
+            #[allow(clippy::cast_precision_loss)]
+            let mean = mean.as_nanos() as f64;
+            mean
+        }
+    }
+}
+
+fn format_samples(timing: &Timing) -> String {
+    let samples = |stats: Option<Measurement>| stats.map_or("-".into(), |s| s.samples.to_string());
+    format!(
+        "{} / {}",
+        samples(timing.part_1_stats),
+        samples(timing.part_2_stats)
+    )
+}
+
+/// The speedup of a day versus its last stored run, e.g. `2.00×` if it got twice as fast.
+fn format_speedup(timing: &Timing) -> Option<String> {
+    let previous = timing.history.last()?;
+    (timing.total_nanos > 0.0).then(|| format!("{:.2}×", previous / timing.total_nanos))
+}
+
+/// Renders values as a unicode sparkline, scaled between their minimum and maximum.
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|value| {
+            if max > min {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let index = ((value - min) / (max - min) * 7.0).round() as usize;
+                BARS[index]
+            } else {
+                BARS[0]
+            }
+        })
+        .collect()
+}
+
+fn cell(value: Option<String>) -> String {
+    format!("`{}`", value.unwrap_or_else(|| "-".into()))
+}
+
+fn construct_table(
+    prefix: &str,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> String {
+    let marker = get_marker(year);
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
+
+    let columns = &config.columns;
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        row(iter::once("Day")
+            .chain(columns.iter().map(|c| c.header()))
+            .map(String::from)
+            .collect()),
+        row(vec![":---:".into(); columns.len() + 1]),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let total_nanos: f64 = timings
+        .data
+        .iter()
+        .map(|t| day_nanos(t, config.statistic))
+        .sum();
+
+    for timing in &timings.data {
+        let path = get_path_for_bin(year, timing.day);
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+        cells.extend(columns.iter().map(|column| match column {
+            Column::Parse | Column::Part1 | Column::Part2 => {
+                cell(part_time(timing, *column, config.statistic))
+            }
+            Column::Samples => cell(Some(format_samples(timing))),
+            Column::Speedup => cell(format_speedup(timing)),
+            Column::Percent => cell((total_nanos > 0.0).then(|| {
+                let share = day_nanos(timing, config.statistic) / total_nanos;
+                format!("{:.1}%", share * 100.0)
+            })),
+            Column::History => {
+                let mut values = timing.history.clone();
+                values.push(timing.total_nanos);
+                let skip = values.len().saturating_sub(config.history.max(1));
+                sparkline(&values[skip..])
+            }
+        }));
+        lines.push(row(cells));
+    }
+
+    // per-part totals, over the days that have statistics.
+    // timings stored by older versions do not have any, and get no totals row.
+    if timings.data.iter().any(|t| t.part_1_stats.is_some()) {
+        let mut cells = vec!["**Total**".to_string()];
+        cells.extend(columns.iter().map(|column| match column {
+            Column::Parse | Column::Part1 | Column::Part2 => {
+                let times: Vec<Duration> = timings
+                    .data
+                    .iter()
+                    .filter_map(|t| part_nanos(t, *column, config.statistic))
+                    .collect();
+                cell((!times.is_empty()).then(|| format!("{:.1?}", times.iter().sum::<Duration>())))
+            }
+            Column::Percent => cell(Some("100.0%".into())),
+            Column::Samples | Column::Speedup | Column::History => String::new(),
+        }));
+        lines.push(row(cells));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    year: Option<Year>,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &get_marker(year))?;
+    let table = construct_table("##", year, timings, total_millis, config);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(year: Option<Year>, timings: Timings, config: &TableConfig) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let total_millis = timings
+        .data
+        .iter()
+        .map(|t| day_nanos(t, config.statistic))
+        .sum::<f64>()
+        / 1_000_000_f64;
+    update_content(&mut readme, year, timings, total_millis, config)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::time::Duration;
+
+    use super::{sparkline, update_content, Column, Statistic, TableConfig, MARKER};
+    use crate::template::record::Measurement;
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 9e+10,
+                    history: vec![],
                 },
             ],
         }
@@ -131,28 +423,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +487,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            None,
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,7 +502,7 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
+            "| :---: | :---: | :---: |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
@@ -180,4 +514,88 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_table_of_year() {
+        let mut s = format!(
+            "{}{}\n<!--- benchmarking table 2019 ---><!--- benchmarking table 2019 --->",
+            MARKER, MARKER
+        );
+        update_content(
+            &mut s,
+            Some(year!(2019)),
+            get_mock_timings(),
+            190.0,
+            &TableConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(s.matches("## Benchmarks").count(), 0);
+        assert_eq!(s.matches("## 2019 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2019-01.rs) | `10ms` | `20ms` |"));
+    }
+
+    #[test]
+    fn formats_configured_columns() {
+        let mut timings = get_mock_timings();
+        let stats = |millis, samples| {
+            let mut stats = Measurement::single(Duration::from_millis(millis));
+            stats.mean = Duration::from_millis(millis + 1);
+            stats.samples = samples;
+            Some(stats)
+        };
+        timings.data[0].part_1_stats = stats(10, 100);
+        timings.data[0].part_2_stats = stats(20, 50);
+        timings.data[0].history = vec![1.5e10, 6e10];
+        timings.data[1].history = vec![3.5e10];
+
+        let config = TableConfig {
+            columns: vec![
+                Column::Part1,
+                Column::Samples,
+                Column::Speedup,
+                Column::Percent,
+                Column::History,
+            ],
+            statistic: Statistic::Mean,
+            history: 3,
+        };
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, None, timings, 190.0, &config).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Samples | Speedup | % of total | History |"
+        );
+        assert_eq!(
+            lines[4],
+            "| :---: | :---: | :---: | :---: | :---: | :---: |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `11.0ms` | `100 / 50` | `2.00×` | `0.0%` | ▁█▃ |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `- / -` | `0.50×` | `43.7%` | ▁█ |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `- / -` | `-` | `56.2%` | ▁ |"
+        );
+        assert_eq!(lines[8], "| **Total** | `11.0ms` |  |  | `100.0%` |  |");
+    }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!("part_1".parse(), Ok(Column::Part1));
+        assert_eq!("history".parse(), Ok(Column::History));
+        assert!("part_3".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn draws_sparklines() {
+        assert_eq!(sparkline(&[1.0, 8.0, 4.5, 1.0]), "▁█▅▁");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
 }
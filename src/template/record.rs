@@ -0,0 +1,510 @@
+/// Machine-readable results of solution parts, as printed by `--json`.
+use std::{collections::HashMap, fmt::Display, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Verdict;
+use crate::template::{failure, Day, Failure};
+
+/// Whether a solution part produced an answer, and why not.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    /// The part ran, but found no answer.
+    Unsolved,
+    NotImplemented,
+    ParseFailed,
+    /// The part returned any other error.
+    Failed,
+}
+
+impl PartStatus {
+    /// The status of a part that returned an error.
+    pub fn of_error(error: &anyhow::Error) -> Self {
+        match failure(error) {
+            Some(Failure::NoAnswer) => PartStatus::Unsolved,
+            Some(Failure::NotImplemented) => PartStatus::NotImplemented,
+            Some(Failure::Parse) => PartStatus::ParseFailed,
+            None => PartStatus::Failed,
+        }
+    }
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::NotImplemented => "not_implemented",
+            PartStatus::ParseFailed => "parse_failed",
+            PartStatus::Failed => "failed",
+        })
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "not_implemented" => Ok(PartStatus::NotImplemented),
+            "parse_failed" => Ok(PartStatus::ParseFailed),
+            "failed" => Ok(PartStatus::Failed),
+            s => Err(format!("unknown part status `{s}`.")),
+        }
+    }
+}
+
+/// Execution time of a solution part, aggregated over all samples.
+/// Outliers are dropped before the statistics are computed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    pub samples: u128,
+    pub outliers: u128,
+}
+
+impl Measurement {
+    /// A measurement made up of a single run.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            stddev: Duration::ZERO,
+            p95: duration,
+            samples: 1,
+            outliers: 0,
+        }
+    }
+
+    /// Compute statistics over a set of benchmark samples.
+    /// Samples outside of 1.5 times the interquartile range are treated as outliers.
+    /// Without samples, all statistics are zero.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let (Some(q1), Some(q3)) = (percentile(&sorted, 25.0), percentile(&sorted, 75.0)) else {
+            return Self {
+                samples: 0,
+                ..Self::single(Duration::ZERO)
+            };
+        };
+
+        let fence = (q3 - q1) * 3 / 2;
+        let (low, high) = (q1.saturating_sub(fence), q3 + fence);
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .copied()
+            .filter(|d| (low..=high).contains(d))
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let values: Vec<f64> = kept.iter().map(|d| d.as_nanos() as f64).collect();
+        #[allow(clippy::cast_precision_loss)]
+        let len = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / len;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let from_nanos = |x: f64| Duration::from_nanos(x.round() as u64);
+
+        Self {
+            mean: from_nanos(mean),
+            median: percentile(&kept, 50.0).unwrap_or_default(),
+            min: kept.first().copied().unwrap_or_default(),
+            max: kept.last().copied().unwrap_or_default(),
+            stddev: from_nanos(variance.sqrt()),
+            p95: percentile(&kept, 95.0).unwrap_or_default(),
+            samples: samples.len() as u128,
+            outliers: (samples.len() - kept.len()) as u128,
+        }
+    }
+}
+
+/// Linearly interpolated percentile of a sorted slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[Duration], p: f64) -> Option<Duration> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = p / 100.0 * last as f64;
+    let (lower, upper) = (
+        sorted[rank.floor() as usize].as_nanos() as f64,
+        sorted[rank.ceil() as usize].as_nanos() as f64,
+    );
+    let nanos = lower + (upper - lower) * (rank - rank.floor());
+    Some(Duration::from_nanos(nanos.round() as u64))
+}
+
+/// The part number under which the parse phase of a day is recorded.
+pub const PARSE_PHASE: u8 = 0;
+
+/// The result of running a single solution part, or the parse phase of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: PartStatus,
+    /// The error chain of a part that failed, e.g. `failed to parse input: invalid digit`.
+    pub error: Option<String>,
+    pub verdict: Verdict,
+    pub time: Measurement,
+}
+
+impl PartRecord {
+    pub fn new<T: Display>(day: Day, part: u8, result: Option<&T>, time: Measurement) -> Self {
+        Self {
+            day,
+            part,
+            answer: result.map(ToString::to_string),
+            status: if result.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            },
+            error: None,
+            verdict: Verdict::Unknown,
+            time,
+        }
+    }
+
+    /// Creates the record of a part that returned a result, keeping the error of failed parts.
+    pub fn from_result<T: Display>(
+        day: Day,
+        part: u8,
+        result: Result<&T, &anyhow::Error>,
+        time: Measurement,
+    ) -> Self {
+        let mut record = Self::new(day, part, result.ok(), time);
+        if let Err(e) = result {
+            record.status = PartStatus::of_error(e);
+            record.error = Some(format!("{e:#}"));
+        }
+        record
+    }
+
+    /// Creates the record of a day's parse phase, which is solved if the input could be parsed.
+    pub fn parse_phase<T>(day: Day, result: &anyhow::Result<T>, time: Measurement) -> Self {
+        let mut record = Self::from_result(day, PARSE_PHASE, result.as_ref().map(|_| &""), time);
+        record.answer = None;
+        record
+    }
+
+    /// Serialize the record to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part records only contain finite numbers")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn nanos(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+impl From<&Measurement> for JsonValue {
+    fn from(value: &Measurement) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), nanos(value.mean));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("max_nanos".into(), nanos(value.max));
+        map.insert("stddev_nanos".into(), nanos(value.stddev));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Measurement {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected measurement to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected measurement.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Measurement {
+            mean: duration("nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            stddev: duration("stddev_nanos")?,
+            p95: duration("p95_nanos")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        // the measurement is flattened into the record.
+        let mut map = match JsonValue::from(&value.time) {
+            JsonValue::Object(map) => map,
+            _ => unreachable!("measurements serialize to objects"),
+        };
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.status to be a string.")?
+            .parse()?;
+
+        // records of older versions have no error.
+        let error = match json.get("error") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.error to be null or string.")?,
+            ),
+        };
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected record.verdict to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartRecord {
+            day,
+            part: part as u8,
+            answer: answer.cloned(),
+            status,
+            error: error.cloned(),
+            verdict,
+            time: Measurement::try_from(value)?,
+        })
+    }
+}
+
+impl FromStr for PartRecord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON."))?;
+        PartRecord::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Measurement, PartRecord, PartStatus, PARSE_PHASE};
+    use crate::{
+        day,
+        template::{answers::Verdict, Failure},
+    };
+
+    fn get_mock_record(answer: Option<&str>) -> PartRecord {
+        PartRecord::new(
+            day!(15),
+            2,
+            answer.as_ref(),
+            Measurement {
+                mean: Duration::from_nanos(74_130),
+                median: Duration::from_nanos(72_000),
+                min: Duration::from_nanos(70_000),
+                max: Duration::from_nanos(90_000),
+                stddev: Duration::from_nanos(1_500),
+                p95: Duration::from_nanos(88_000),
+                samples: 100,
+                outliers: 2,
+            },
+        )
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let record = get_mock_record(Some("42"));
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(parsed.status, PartStatus::Solved);
+    }
+
+    #[test]
+    fn round_trips_verdicts() {
+        let mut record = get_mock_record(Some("42"));
+        record.verdict = Verdict::Incorrect;
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed.verdict, Verdict::Incorrect);
+    }
+
+    #[test]
+    fn round_trips_answers_with_patterns() {
+        let record = get_mock_record(Some("@ ( ) ms (2s @ 5 samples)\n\"µs\""));
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed.answer, record.answer);
+    }
+
+    #[test]
+    fn round_trips_unsolved_parts() {
+        let record = get_mock_record(None);
+        let line = record.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        let parsed: PartRecord = line.parse().unwrap();
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.status, PartStatus::Unsolved);
+    }
+
+    #[test]
+    fn round_trips_failed_parts() {
+        let result: anyhow::Result<u32> =
+            Err(anyhow::Error::from(Failure::Parse).context("line 3"));
+        let record =
+            PartRecord::from_result(day!(15), 1, result.as_ref(), get_mock_record(None).time);
+        let parsed: PartRecord = record.to_json_line().parse().unwrap();
+        assert_eq!(parsed.status, PartStatus::ParseFailed);
+        assert_eq!(
+            parsed.error.as_deref(),
+            Some("line 3: failed to parse input")
+        );
+    }
+
+    #[test]
+    fn records_parse_phases() {
+        let time = get_mock_record(None).time;
+        let record = PartRecord::parse_phase(day!(15), &Ok(vec![1, 2]), time);
+        assert_eq!(record.part, PARSE_PHASE);
+        assert_eq!(record.status, PartStatus::Solved);
+        assert_eq!(record.answer, None);
+
+        let result: anyhow::Result<()> = Err(anyhow::anyhow!("bad line"));
+        let record = PartRecord::parse_phase(day!(15), &result, time);
+        assert_eq!(record.status, PartStatus::Failed);
+        assert_eq!(record.error.as_deref(), Some("bad line"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        r#"{ "day": "01", "part": 1 }"#.parse::<PartRecord>().unwrap();
+    }
+
+    mod measurement {
+        use std::time::Duration;
+
+        use crate::template::record::Measurement;
+
+        fn nanos(values: &[u64]) -> Vec<Duration> {
+            values.iter().copied().map(Duration::from_nanos).collect()
+        }
+
+        #[test]
+        fn computes_statistics() {
+            let m = Measurement::from_samples(&nanos(&[40, 10, 30, 20, 50]));
+            assert_eq!(m.mean, Duration::from_nanos(30));
+            assert_eq!(m.median, Duration::from_nanos(30));
+            assert_eq!(m.min, Duration::from_nanos(10));
+            assert_eq!(m.max, Duration::from_nanos(50));
+            assert_eq!(m.p95, Duration::from_nanos(48));
+            assert_eq!(m.stddev.as_nanos(), 14);
+            assert_eq!(m.samples, 5);
+            assert_eq!(m.outliers, 0);
+        }
+
+        #[test]
+        fn drops_outliers() {
+            let m = Measurement::from_samples(&nanos(&[10, 11, 12, 11, 10, 12, 11, 1_000]));
+            assert_eq!(m.outliers, 1);
+            assert_eq!(m.samples, 8);
+            assert_eq!(m.max, Duration::from_nanos(12));
+            assert_eq!(m.median, Duration::from_nanos(11));
+        }
+
+        #[test]
+        fn handles_empty_samples() {
+            let m = Measurement::from_samples(&[]);
+            assert_eq!(m.mean, Duration::ZERO);
+            assert_eq!(m.samples, 0);
+            assert_eq!(m.outliers, 0);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+/// Typed entries that let a single binary call every day's solution directly.
+use crate::template::record::PartRecord;
+use crate::template::runner::RunOptions;
+use crate::template::{Day, Overrides, Year};
+
+/// All registered parts of a day.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    /// `None` for the workspace's default year.
+    pub year: Option<Year>,
+    pub day: Day,
+    /// The numbers of the parts the day implements.
+    pub parts: &'static [u8],
+    /// Runs the given parts against an input and prints their results, with `key=value`
+    /// overrides of the day's [`Params`](crate::template::Params). Days with a parse phase
+    /// parse the input once and record it as [`PARSE_PHASE`](crate::template::record::PARSE_PHASE).
+    /// Parts that panic are left out.
+    pub run: fn(&str, &[u8], &Overrides, RunOptions) -> Vec<PartRecord>,
+}
+
+/// Looks up the registered solution for `day` of `year`, if it has been scaffolded.
+pub fn find_solution(solutions: &[Solution], year: Option<Year>, day: Day) -> Option<&Solution> {
+    solutions
+        .iter()
+        .find(|solution| solution.year == year && solution.day == day)
+}
@@ -0,0 +1,381 @@
+/// Reports of a multi-day run for other tools, as printed by `cargo all --format <format>`.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::record::{nanos, PartRecord, PartStatus, PARSE_PHASE};
+use crate::template::run_multi::{DayOutcome, DaySummary, MultiRun};
+use crate::template::{Day, Year};
+
+/// The format of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    /// A table to paste into issues or the readme.
+    Markdown,
+    /// JUnit XML, for test dashboards.
+    Junit,
+}
+
+impl Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Markdown => "markdown",
+            ReportFormat::Junit => "junit",
+        })
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(ReportFormat::Json),
+            "markdown" => Ok(ReportFormat::Markdown),
+            "junit" => Ok(ReportFormat::Junit),
+            s => Err(format!(
+                "unknown format `{s}`, expected `json`, `markdown` or `junit`."
+            )),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// Renders the answers, timings and verdicts of every day and part of a run.
+    pub fn render(self, run: &MultiRun, year: Option<Year>) -> String {
+        match self {
+            ReportFormat::Json => json_report(run),
+            ReportFormat::Markdown => markdown_report(run),
+            ReportFormat::Junit => junit_report(run, |day| Answers::read(year, day)),
+        }
+    }
+}
+
+fn day_records(run: &MultiRun, day: Day) -> impl Iterator<Item = &PartRecord> {
+    run.records.iter().filter(move |r| r.day == day)
+}
+
+fn part_name(record: &PartRecord) -> String {
+    match record.part {
+        PARSE_PHASE => "parse".into(),
+        part => part.to_string(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn json_report(run: &MultiRun) -> String {
+    let days = run
+        .days
+        .iter()
+        .map(|day| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("day".into(), JsonValue::String(day.day.to_string()));
+            map.insert("outcome".into(), JsonValue::String(day.outcome.to_string()));
+            map.insert(
+                "elapsed_nanos".into(),
+                day.elapsed.map_or(JsonValue::Null, nanos),
+            );
+            map.insert(
+                "parts".into(),
+                JsonValue::Array(day_records(run, day.day).map(JsonValue::from).collect()),
+            );
+            JsonValue::Object(map)
+        })
+        .collect();
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("days".into(), JsonValue::Array(days));
+    JsonValue::Object(map)
+        .format()
+        .expect("reports only contain finite numbers")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn markdown_report(run: &MultiRun) -> String {
+    let mut lines = vec![
+        "| Day | Part | Answer | Status | Verdict | Time |".to_string(),
+        "| :---: | :---: | :--- | :--- | :---: | ---: |".to_string(),
+    ];
+
+    for day in &run.days {
+        let mut has_records = false;
+        for record in day_records(run, day.day) {
+            has_records = true;
+            let verdict = match record.verdict {
+                Verdict::Correct => "✔",
+                Verdict::Incorrect => "✘",
+                Verdict::Unknown => "",
+            };
+            lines.push(format!(
+                "| {} | {} | {} | {} | {verdict} | {} |",
+                day.day,
+                part_name(record),
+                record
+                    .answer
+                    .as_deref()
+                    .map(escape_markdown)
+                    .unwrap_or_default(),
+                record.status,
+                format_duration(record.time.median),
+            ));
+        }
+
+        // days that did not run through, or did not run at all, get a row of their own.
+        if !has_records || day.outcome != DayOutcome::Ok {
+            lines.push(format!(
+                "| {} | | | {} | | {} |",
+                day.day,
+                day.outcome,
+                day.elapsed.map(format_duration).unwrap_or_default(),
+            ));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{duration:.1?}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How a testcase of a JUnit report ended.
+enum TestResult {
+    Passed,
+    Failure(String),
+    Error(String),
+    Skipped(String),
+}
+
+struct TestCase {
+    name: String,
+    time: Duration,
+    result: TestResult,
+}
+
+impl TestCase {
+    fn of_record(record: &PartRecord, answers: &Answers) -> Self {
+        let error = || record.error.clone().unwrap_or_default();
+        let result = match (record.status, record.verdict) {
+            (PartStatus::Solved, Verdict::Incorrect) => TestResult::Failure(format!(
+                "expected {}, got {}",
+                answers.get(record.part).unwrap_or_default(),
+                record.answer.as_deref().unwrap_or_default()
+            )),
+            (PartStatus::Solved, _) => TestResult::Passed,
+            (PartStatus::Unsolved, _) => TestResult::Failure(error()),
+            (PartStatus::NotImplemented, _) => TestResult::Skipped(error()),
+            (PartStatus::ParseFailed | PartStatus::Failed, _) => TestResult::Error(error()),
+        };
+        let name = match record.part {
+            PARSE_PHASE => "parse".into(),
+            part => format!("part_{part}"),
+        };
+        Self {
+            name,
+            time: record.time.median,
+            result,
+        }
+    }
+
+    /// The testcase of a day that did not run through, if it did not.
+    fn of_day(day: &DaySummary) -> Option<Self> {
+        let result = match day.outcome {
+            DayOutcome::Ok => return None,
+            DayOutcome::Timeout => TestResult::Error("timed out".into()),
+            DayOutcome::Panicked => TestResult::Error("panicked".into()),
+            DayOutcome::NotSolved => TestResult::Skipped("not solved".into()),
+        };
+        Some(Self {
+            name: "day".into(),
+            time: day.elapsed.unwrap_or_default(),
+            result,
+        })
+    }
+
+    fn to_xml(&self, classname: &str) -> String {
+        let open = format!(
+            "    <testcase classname=\"{classname}\" name=\"{}\" time=\"{:.6}\"",
+            self.name,
+            self.time.as_secs_f64()
+        );
+        let (tag, message) = match &self.result {
+            TestResult::Passed => return format!("{open}/>"),
+            TestResult::Failure(message) => ("failure", message),
+            TestResult::Error(message) => ("error", message),
+            TestResult::Skipped(message) => ("skipped", message),
+        };
+        format!(
+            "{open}>\n      <{tag} message=\"{}\"/>\n    </testcase>",
+            escape_xml(message)
+        )
+    }
+}
+
+fn junit_report(run: &MultiRun, answers: impl Fn(Day) -> Answers) -> String {
+    let mut suites = vec![];
+    let mut counts = [0; 4];
+
+    for day in &run.days {
+        let answers = answers(day.day);
+        let cases: Vec<TestCase> = day_records(run, day.day)
+            .map(|record| TestCase::of_record(record, &answers))
+            .chain(TestCase::of_day(day))
+            .collect();
+
+        let count = |f: fn(&TestResult) -> bool| cases.iter().filter(|c| f(&c.result)).count();
+        let suite_counts = [
+            cases.len(),
+            count(|r| matches!(r, TestResult::Failure(_))),
+            count(|r| matches!(r, TestResult::Error(_))),
+            count(|r| matches!(r, TestResult::Skipped(_))),
+        ];
+        for (total, count) in counts.iter_mut().zip(suite_counts) {
+            *total += count;
+        }
+
+        let classname = format!("day_{}", day.day);
+        let mut suite = vec![format!(
+            "  <testsuite name=\"Day {}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            day.day,
+            suite_counts[0],
+            suite_counts[1],
+            suite_counts[2],
+            suite_counts[3],
+            day.elapsed.unwrap_or_default().as_secs_f64()
+        )];
+        suite.extend(cases.iter().map(|case| case.to_xml(&classname)));
+        suite.push("  </testsuite>".into());
+        suites.push(suite.join("\n"));
+    }
+
+    let time: Duration = run.days.iter().filter_map(|day| day.elapsed).sum();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"advent_of_code\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.6}\">\n\
+         {}\n\
+         </testsuites>",
+        counts[0],
+        counts[1],
+        counts[2],
+        counts[3],
+        time.as_secs_f64(),
+        suites.join("\n")
+    )
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+        .replace('\n', "&#10;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{escape_xml, junit_report, markdown_report, ReportFormat};
+    use crate::day;
+    use crate::template::answers::{Answers, Verdict};
+    use crate::template::record::{Measurement, PartRecord, PartStatus};
+    use crate::template::run_multi::{DayOutcome, DaySummary, MultiRun};
+
+    fn run() -> MultiRun {
+        let time = Measurement::single(Duration::from_micros(12));
+        let mut correct = PartRecord::new(day!(1), 1, Some(&"42"), time);
+        correct.verdict = Verdict::Correct;
+        let mut incorrect = PartRecord::new(day!(1), 2, Some(&"a|b"), time);
+        incorrect.verdict = Verdict::Incorrect;
+        let mut failed = PartRecord::new::<u32>(day!(2), 1, None, time);
+        failed.status = PartStatus::Failed;
+        failed.error = Some("bad <input>".into());
+
+        let summary = |day, outcome, elapsed| DaySummary {
+            day,
+            outcome,
+            elapsed,
+        };
+        MultiRun {
+            records: vec![correct, incorrect, failed],
+            timings: None,
+            days: vec![
+                summary(day!(1), DayOutcome::Ok, Some(Duration::from_millis(1))),
+                summary(
+                    day!(2),
+                    DayOutcome::Panicked,
+                    Some(Duration::from_millis(2)),
+                ),
+                summary(day!(3), DayOutcome::NotSolved, None),
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ReportFormat::from_str("junit"), Ok(ReportFormat::Junit));
+        assert!(ReportFormat::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = JsonValue::from_str(&ReportFormat::Json.render(&run(), None)).unwrap();
+        let days: &Vec<JsonValue> = json["days"].get().unwrap();
+        assert_eq!(days.len(), 3);
+        let parts: &Vec<JsonValue> = days[0]["parts"].get().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1]["verdict"], JsonValue::String("incorrect".into()));
+        assert_eq!(days[1]["outcome"], JsonValue::String("panicked".into()));
+        assert_eq!(days[2]["elapsed_nanos"], JsonValue::Null);
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = markdown_report(&run());
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(lines[2], "| 01 | 1 | 42 | solved | ✔ | 12.0µs |");
+        assert_eq!(lines[3], "| 01 | 2 | a\\|b | solved | ✘ | 12.0µs |");
+        assert_eq!(lines[4], "| 02 | 1 |  | failed |  | 12.0µs |");
+        assert_eq!(lines[5], "| 02 | | | panicked | | 2.0ms |");
+        assert_eq!(lines[6], "| 03 | | | not solved | |  |");
+    }
+
+    #[test]
+    fn renders_junit() {
+        let answers = |_| Answers::try_from("{\"part_2\":\"a\"}".to_string()).unwrap();
+        let xml = junit_report(&run(), answers);
+        assert!(xml.contains(
+            "<testsuites name=\"advent_of_code\" tests=\"5\" failures=\"1\" errors=\"2\" skipped=\"1\""
+        ));
+        assert!(xml.contains("<testcase classname=\"day_01\" name=\"part_1\" time=\"0.000012\"/>"));
+        assert!(xml.contains("<failure message=\"expected a, got a|b\"/>"));
+        assert!(xml.contains("<error message=\"bad &lt;input&gt;\"/>"));
+        assert!(xml.contains("<error message=\"panicked\"/>"));
+        assert!(xml.contains("<skipped message=\"not solved\"/>"));
+    }
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!(escape_xml("\"a\" & <b>"), "&quot;a&quot; &amp; &lt;b&gt;");
+    }
+}
@@ -1,257 +1,449 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::any::Any;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use std::{panic, thread};
+
+use crate::template::output::{out, outln};
+use crate::template::record::{PartRecord, PartStatus, PARSE_PHASE};
+use crate::template::{
+    answers::Verdict, data_folder, find_solution, output, runner::RunOptions, try_read_file, Day,
+    Overrides, Solution, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How a multi-day run executes the days.
+#[derive(Clone, Copy, Debug)]
+pub struct MultiRunOptions {
+    /// The number of days that run in parallel.
+    pub jobs: usize,
+    /// Days that take longer are abandoned and count as failed.
+    pub timeout: Option<Duration>,
+}
 
-    let mut need_space = false;
+impl Default for MultiRunOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 1,
+            timeout: None,
+        }
+    }
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// How a day of a multi-day run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayOutcome {
+    Ok,
+    Timeout,
+    Panicked,
+    /// The day is not scaffolded or misses an input.
+    NotSolved,
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+impl DayOutcome {
+    /// Whether the day failed, i.e. timed out or panicked.
+    pub fn is_failure(self) -> bool {
+        matches!(self, DayOutcome::Timeout | DayOutcome::Panicked)
+    }
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+impl Display for DayOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DayOutcome::Ok => "ok",
+            DayOutcome::Timeout => "timeout",
+            DayOutcome::Panicked => "panicked",
+            DayOutcome::NotSolved => "not solved",
+        })
+    }
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
+/// A day of a multi-day run.
+struct DayRun {
+    outcome: DayOutcome,
+    records: Vec<PartRecord>,
+    /// The wall time the day took, `None` if it did not run.
+    elapsed: Option<Duration>,
+    /// The panic message of a day that panicked.
+    panic: Option<String>,
+    /// The captured output of a day that ran in parallel.
+    output: String,
+}
+
+impl DayRun {
+    fn not_solved() -> Self {
+        Self {
+            outcome: DayOutcome::NotSolved,
+            records: vec![],
+            elapsed: None,
+            panic: None,
+            output: String::new(),
+        }
+    }
+}
+
+/// How a day of a multi-day run went.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DaySummary {
+    pub day: Day,
+    pub outcome: DayOutcome,
+    /// The wall time the day took, `None` if it did not run.
+    pub elapsed: Option<Duration>,
+}
+
+/// The results of a multi-day run.
+pub struct MultiRun {
+    pub records: Vec<PartRecord>,
+    /// Only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Every day of the run, in order.
+    pub days: Vec<DaySummary>,
+}
+
+impl MultiRun {
+    /// Whether any part did not match its recorded answer.
+    pub fn has_incorrect_answers(&self) -> bool {
+        self.records.iter().any(|r| r.verdict == Verdict::Incorrect)
+    }
+
+    /// Whether any day timed out or panicked.
+    pub fn has_failures(&self) -> bool {
+        self.days.iter().any(|day| day.outcome.is_failure())
+    }
+}
+
+/// Runs the given days of a year in-process, using the solutions registered in the calling binary.
+/// With more than one job, days run in parallel and their output is printed in order once a day
+/// has finished. Prints a summary of the days' outcomes at the end. All output can be captured
+/// with [`output::capture`].
+pub fn run_multi(
+    solutions: &[Solution],
+    year: Option<Year>,
+    days_to_run: &HashSet<Day>,
+    is_timed: bool,
+    options: MultiRunOptions,
+) -> MultiRun {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut all_records: Vec<PartRecord> = vec![];
+    let mut summary: Vec<DaySummary> = vec![];
+
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut collect = |day: Day, run: DayRun| {
+        out!("{}", run.output);
+        match run.outcome {
+            DayOutcome::Ok => {}
+            DayOutcome::NotSolved => outln!("Not solved."),
+            DayOutcome::Timeout => outln!(
+                "{ANSI_ITALIC}Timed out after {:.1?}.{ANSI_RESET}",
+                options.timeout.unwrap_or_default()
+            ),
+            DayOutcome::Panicked => outln!(
+                "{ANSI_ITALIC}Panicked: {}{ANSI_RESET}",
+                run.panic
+                    .as_deref()
+                    .unwrap_or("a part panicked, see the error output")
+            ),
+        }
+        if !run.records.is_empty() {
+            timings.push(Timing::from_records(day, &run.records));
+        }
+        summary.push(DaySummary {
+            day,
+            outcome: run.outcome,
+            elapsed: run.elapsed,
         });
+        all_records.extend(run.records);
+    };
+
+    if options.jobs <= 1 {
+        // a day that timed out keeps running, capture its output so that it does not end up
+        // in the output of the following days. Days run on threads of their own, so they also
+        // need to capture if the caller does.
+        let capture = options.timeout.is_some() || output::is_captured();
+        for (i, &day) in days.iter().enumerate() {
+            print_header(day, i > 0);
+            collect(
+                day,
+                run_solution(solutions, year, day, is_timed, options.timeout, capture),
+            );
+        }
+    } else {
+        run_parallel(solutions, year, &days, is_timed, options, |i, run| {
+            print_header(days[i], i > 0);
+            collect(days[i], run);
+        });
+    }
 
-    if is_timed {
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
+        outln!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
         Some(timings)
     } else {
         None
+    };
+
+    outln!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    outln!("{}", summary_table(&summary));
+
+    MultiRun {
+        records: all_records,
+        timings,
+        days: summary,
     }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    #[allow(dead_code)]
-    IO(io::Error),
+/// Runs the days on `jobs` threads, capturing their output. Hands the results of every day to
+/// `on_finished` in order of the days.
+fn run_parallel(
+    solutions: &[Solution],
+    year: Option<Year>,
+    days: &[Day],
+    is_timed: bool,
+    options: MultiRunOptions,
+    mut on_finished: impl FnMut(usize, DayRun),
+) {
+    let next_day = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next_day = &next_day;
+            scope.spawn(move || loop {
+                let i = next_day.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(i) else {
+                    break;
+                };
+                let run = run_solution(solutions, year, day, is_timed, options.timeout, true);
+                if sender.send((i, run)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // days finish out of order, hold back their results until the preceding days are done.
+        let mut finished = BTreeMap::new();
+        let mut next_to_print = 0;
+        for (i, run) in receiver {
+            finished.insert(i, run);
+            while let Some(run) = finished.remove(&next_to_print) {
+                on_finished(next_to_print, run);
+                next_to_print += 1;
+            }
+        }
+    });
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        outln!();
     }
+    outln!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    outln!("------");
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+fn summary_table(summary: &[DaySummary]) -> String {
+    let mut lines = vec![format!("{:<5} {:<11} {:>10}", "Day", "Outcome", "Time")];
+    for day in summary {
+        let elapsed = day
+            .elapsed
+            .map_or_else(|| "-".into(), |d| format!("{d:.1?}"));
+        lines.push(format!(
+            "{:<5} {:<11} {:>10}",
+            day.day.to_string(),
+            day.outcome.to_string(),
+            elapsed
+        ));
+    }
+    lines.join("\n")
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Run every registered part of a day against its input, on a thread of its own so that the day
+/// can be abandoned once it exceeds the `timeout`. Its output is captured if `capture` is set,
+/// the output of a day that timed out is lost.
+/// Days that are not scaffolded or miss an input are skipped.
+fn run_solution(
+    solutions: &[Solution],
+    year: Option<Year>,
+    day: Day,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    capture: bool,
+) -> DayRun {
+    let Some(&solution) = find_solution(solutions, year, day) else {
+        return DayRun::not_solved();
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    let input = match try_read_file(&data_folder(year, "inputs"), day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not open input file of day {day}: {e}");
+            return DayRun::not_solved();
         }
+    };
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
-
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
+    let options = RunOptions {
+        is_timed,
+        ..RunOptions::default()
+    };
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+    let (sender, receiver) = mpsc::channel();
+    let timer = Instant::now();
+    let spawned = thread::Builder::new()
+        .name(format!("day {day}"))
+        .spawn(move || {
+            // the default panic hook has already reported panics on stderr.
+            let run = || {
+                panic::catch_unwind(|| {
+                    (solution.run)(&input, solution.parts, &Overrides::new(), options)
+                })
+            };
+            let result = if capture {
+                output::capture(run)
+            } else {
+                (run(), String::new())
+            };
+            let _ = sender.send(result);
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
-
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+    if let Err(e) = spawned {
+        eprintln!("could not start a thread for day {day}: {e}");
+        return DayRun::not_solved();
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    // a day that times out keeps running in the background until the process exits.
+    let result = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).ok(),
+        None => receiver.recv().ok(),
+    };
+    let elapsed = Some(timer.elapsed());
+
+    let Some((result, output)) = result else {
+        return DayRun {
+            outcome: DayOutcome::Timeout,
+            elapsed,
+            ..DayRun::not_solved()
         };
+    };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    match result {
+        Ok(records) => DayRun {
+            outcome: if has_missing_parts(&solution, &records) {
+                DayOutcome::Panicked
+            } else {
+                DayOutcome::Ok
+            },
+            records,
+            elapsed,
+            panic: None,
+            output,
+        },
+        Err(payload) => DayRun {
+            outcome: DayOutcome::Panicked,
+            elapsed,
+            panic: panic_message(payload.as_ref()),
+            output,
+            ..DayRun::not_solved()
+        },
+    }
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+/// Whether a part is missing from the records because it panicked. Parts are skipped on purpose
+/// if parsing failed.
+fn has_missing_parts(solution: &Solution, records: &[PartRecord]) -> bool {
+    let parse_failed = records
+        .iter()
+        .any(|r| r.part == PARSE_PHASE && r.status != PartStatus::Solved);
+    !parse_failed
+        && solution
+            .parts
+            .iter()
+            .any(|part| !records.iter().any(|r| r.part == *part))
+}
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+fn panic_message(payload: &(dyn Any + Send)) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+}
 
-                timings.total_nanos += nanos;
-            });
+/* -------------------------------------------------------------------------- */
 
-        timings
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    use super::{has_missing_parts, summary_table, DayOutcome, DaySummary};
+    use crate::day;
+    use crate::template::record::{Measurement, PartRecord, PARSE_PHASE};
+    use crate::template::Solution;
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn solution() -> Solution {
+        Solution {
+            year: None,
+            day: day!(1),
+            parts: &[1, 2],
+            run: |_, _, _, _| vec![],
+        }
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn record(part: u8, answer: Option<&str>) -> PartRecord {
+        PartRecord::new(
+            day!(1),
+            part,
+            answer.as_ref(),
+            Measurement::single(Duration::ZERO),
+        )
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn detects_parts_that_panicked() {
+        let solution = solution();
+        let solved = [record(1, Some("1")), record(2, Some("2"))];
+        assert!(!has_missing_parts(&solution, &solved));
+        assert!(has_missing_parts(&solution, &solved[..1]));
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+        // parts are skipped on purpose when parsing failed.
+        let parse_failed = [record(PARSE_PHASE, None)];
+        assert!(!has_missing_parts(&solution, &parse_failed));
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn renders_summary_table() {
+        let summary = |day, outcome, elapsed| DaySummary {
+            day,
+            outcome,
+            elapsed,
+        };
+        let table = summary_table(&[
+            summary(day!(1), DayOutcome::Ok, Some(Duration::from_millis(12))),
+            summary(day!(2), DayOutcome::Timeout, Some(Duration::from_secs(10))),
+            summary(day!(3), DayOutcome::NotSolved, None),
+        ]);
+        assert_eq!(
+            table,
+            [
+                "Day   Outcome           Time",
+                "01    ok              12.0ms",
+                "02    timeout          10.0s",
+                "03    not solved           -",
+            ]
+            .join("\n")
+        );
+        assert!(DayOutcome::Panicked.is_failure());
+        assert!(!DayOutcome::NotSolved.is_failure());
     }
 }
@@ -1,25 +1,208 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::{Answers, Verdict};
+use crate::template::aoc_client::{self, AocClientError, Submission, SubmissionVerdict};
+use crate::template::output::{outln, progress};
+use crate::template::record::{Measurement, PartRecord};
+use crate::template::submissions::{self, Entry, SubmissionLog};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    failure, overrides_from_args, Day, Failure, Input, IntoAnswer, Params, Year, ANSI_ITALIC,
+    ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Controls how a solution part is executed and reported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once.
+    pub is_timed: bool,
+    /// Print one JSON record per part instead of human-readable output.
+    pub is_json: bool,
+    /// The input is not the puzzle input, so the results are neither verified nor submitted.
+    pub is_custom_input: bool,
+}
+
+impl RunOptions {
+    /// Read the options from the arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        Self {
+            is_timed: env::args().any(|x| x == "--time"),
+            is_json: env::args().any(|x| x == "--json"),
+            is_custom_input: env::args().any(|x| x == "--input" || x == "--example"),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+) -> PartRecord {
+    let options = RunOptions::from_args();
+    let (result, record) = execute_part(func, input, year, day, part, options);
+
+    if let Ok(result) = result {
+        match submit_result(&result, year, day, part, options) {
+            Some(Ok(submission)) => {
+                println!("{}", submission.message);
+                if submission.verdict == SubmissionVerdict::Correct {
+                    record_answer(year, day, part, &result.to_string());
+                }
+            }
+            Some(Err(e)) => eprintln!("Failed to submit result: {e}"),
+            None => {}
+        }
+    }
+
+    record
+}
+
+/// Read the input of a day from the source selected by the `--input` and `--example` arguments.
+/// Exits if it can not be read.
+pub fn input_from_args(year: Option<Year>, day: Day) -> String {
+    match Input::from_args().and_then(|input| input.read(year, day)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Invalid input: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Read the parameters of a day, overridden by the `--param key=value` arguments.
+/// Exits if an override does not apply to the parameters.
+pub fn params_from_args<P: Params>() -> P {
+    match overrides_from_args().and_then(|overrides| P::with_overrides(&overrides)) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Invalid parameters: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Exit with a non-zero status code if any part did not match its recorded answer.
+pub fn check_answers(records: &[PartRecord]) {
+    if records.iter().any(|r| r.verdict == Verdict::Incorrect) {
+        eprintln!("One or more answers do not match the recorded answers.");
+        process::exit(1);
+    }
+}
+
+/// Store a known-correct answer in `data/answers`.
+pub fn record_answer(year: Option<Year>, day: Day, part: u8, answer: &str) {
+    match Answers::record(year, day, part, answer) {
+        Ok(()) => println!("Recorded answer for day {day}, part {part}."),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+/// Run a solution part in-process and report its answer and execution time.
+/// Returns `None` if the part panicked, the default panic hook has already reported it on stderr.
+pub fn run_registered<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    options: RunOptions,
+) -> Option<PartRecord> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        execute_part(func, input, year, day, part, options).1
+    }))
+    .ok()
+}
+
+/// Run the parse phase of a day and report its execution time.
+/// Returns the parsed input that is handed to both parts, `None` if parsing failed.
+pub fn run_parse<P, E: Into<anyhow::Error>>(
+    func: impl Fn(&str) -> Result<P, E>,
+    input: &str,
+    day: Day,
+    options: RunOptions,
+) -> (Option<P>, PartRecord) {
+    let (result, time) = run_timed(
+        |input| {
+            func(input).map_err(|e| {
+                let e: anyhow::Error = e.into();
+                match failure(&e) {
+                    Some(_) => e,
+                    None => e.context(Failure::Parse),
+                }
+            })
+        },
+        input,
+        |_| {
+            if !options.is_json {
+                progress!("Parse:");
+            }
+        },
+        options,
+    );
+
+    let record = PartRecord::parse_phase(day, &result, time);
+
+    if options.is_json {
+        outln!("{}", record.to_json_line());
+    } else {
+        progress!("\r");
+        match &result {
+            Ok(_) => outln!("Parse:{}", format_duration(&time)),
+            Err(e) => outln!("Parse: ✖ {ANSI_ITALIC}{e:#}{ANSI_RESET}             "),
+        }
+    }
+
+    (result.ok(), record)
+}
+
+fn execute_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    options: RunOptions,
+) -> (anyhow::Result<R::Answer>, PartRecord) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, time) = run_timed(
+        |input| func(input).into_answer(),
+        input,
+        |result| {
+            if !options.is_json {
+                print_result(result, &part_str, "", "");
+            }
+        },
+        options,
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut record = PartRecord::from_result(day, part, result.as_ref(), time);
+    let answers = if options.is_custom_input {
+        Answers::default()
+    } else {
+        Answers::read(year, day)
+    };
+    record.verdict = answers.verify(part, record.answer.as_deref());
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if options.is_json {
+        outln!("{}", record.to_json_line());
+    } else {
+        print_result(
+            &result,
+            &part_str,
+            &format_verdict(record.verdict, answers.get(part)),
+            &format_duration(&time),
+        );
     }
+
+    (result, record)
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
@@ -29,7 +212,8 @@ fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: RunOptions,
+) -> (T, Measurement) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,24 +227,33 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let run = if options.is_timed {
+        bench(func, input, &base_time, options)
     } else {
-        (base_time, 1)
+        Measurement::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, run)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: RunOptions,
+) -> Measurement {
+    if !options.is_json {
+        progress!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm up caches and branch predictors before measuring.
+    for _ in 0..bench_iterations.div_ceil(10) {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,72 +264,82 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Measurement::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
+pub(crate) fn format_duration(time: &Measurement) -> String {
+    let duration = time.median;
+    if time.samples == 1 {
         format!(" ({duration:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({duration:.1?} ± {:.1?} @ {} samples)",
+            time.stddev, time.samples
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn format_verdict(verdict: Verdict, expected: Option<&str>) -> String {
+    match (verdict, expected) {
+        (Verdict::Correct, _) => " ✔".into(),
+        (Verdict::Incorrect, Some(expected)) if !expected.contains('\n') => {
+            format!(" ✘ (expected: {expected})")
+        }
+        (Verdict::Incorrect, _) => " ✘".into(),
+        (Verdict::Unknown, _) => String::new(),
+    }
+}
+
+fn print_result<T: Display>(
+    result: &anyhow::Result<T>,
+    part: &str,
+    verdict_str: &str,
+    duration_str: &str,
+) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼{verdict_str} {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    progress!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    progress!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str =
+                    format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{verdict_str}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    progress!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    progress!("\r");
+                    outln!("{str}");
                 }
             }
         }
-        None => {
+        Err(e) => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                progress!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                // prints the error chain, e.g. `failed to parse input: invalid digit found in string`.
+                progress!("\r");
+                outln!("{part}: ✖ {ANSI_ITALIC}{e:#}{ANSI_RESET}{verdict_str}             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Parse the arguments passed to `solve` and try to submit one part of the solution.
 fn submit_result<T: Display>(
-    result: T,
+    result: &T,
+    year: Option<Year>,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    options: RunOptions,
+) -> Option<Result<Submission, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +362,28 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if options.is_custom_input {
+        eprintln!("Refusing to submit result: it was not computed from the puzzle input.");
+        return None;
+    }
+
+    let answer = result.to_string();
+    let now = submissions::now();
+
+    if let Err(refusal) = SubmissionLog::read(year, day).check(part, &answer, now) {
+        eprintln!("Refusing to submit result: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let submission = aoc_client::submit(year, day, part, &answer);
+
+    if let Ok(submission) = &submission {
+        if let Err(e) = SubmissionLog::append(year, day, Entry::new(part, &answer, submission, now))
+        {
+            eprintln!("Failed to log submission: {e}");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(submission)
 }
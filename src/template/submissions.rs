@@ -0,0 +1,429 @@
+/// Module that keeps a log of submitted answers and refuses submissions that are known to fail.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Hint, Submission, SubmissionVerdict};
+use crate::template::{data_folder, Day, Year};
+
+/// The feedback the website gave on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feedback {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    TooRecent,
+    WrongLevel,
+    Unknown,
+}
+
+impl From<&SubmissionVerdict> for Feedback {
+    fn from(value: &SubmissionVerdict) -> Self {
+        match value {
+            SubmissionVerdict::Correct => Feedback::Correct,
+            SubmissionVerdict::Incorrect {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => Feedback::TooHigh,
+            SubmissionVerdict::Incorrect {
+                hint: Some(Hint::TooLow),
+                ..
+            } => Feedback::TooLow,
+            SubmissionVerdict::Incorrect { hint: None, .. } => Feedback::Incorrect,
+            SubmissionVerdict::TooRecent { .. } => Feedback::TooRecent,
+            SubmissionVerdict::WrongLevel => Feedback::WrongLevel,
+            SubmissionVerdict::Unknown => Feedback::Unknown,
+        }
+    }
+}
+
+impl Display for Feedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Feedback::Correct => "correct",
+            Feedback::Incorrect => "incorrect",
+            Feedback::TooHigh => "too_high",
+            Feedback::TooLow => "too_low",
+            Feedback::TooRecent => "too_recent",
+            Feedback::WrongLevel => "wrong_level",
+            Feedback::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Feedback {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Feedback::Correct),
+            "incorrect" => Ok(Feedback::Incorrect),
+            "too_high" => Ok(Feedback::TooHigh),
+            "too_low" => Ok(Feedback::TooLow),
+            "too_recent" => Ok(Feedback::TooRecent),
+            "wrong_level" => Ok(Feedback::WrongLevel),
+            "unknown" => Ok(Feedback::Unknown),
+            s => Err(format!("unknown feedback `{s}`.")),
+        }
+    }
+}
+
+impl Feedback {
+    /// Whether the feedback proves the answer to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Feedback::Incorrect | Feedback::TooHigh | Feedback::TooLow
+        )
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+    /// Unix timestamp in seconds.
+    pub submitted_at: u64,
+    /// Seconds the website asked to wait before the next submission.
+    pub wait: Option<u64>,
+}
+
+impl Entry {
+    pub fn new(part: u8, answer: &str, submission: &Submission, submitted_at: u64) -> Self {
+        let wait = match submission.verdict {
+            SubmissionVerdict::Incorrect { wait, .. } | SubmissionVerdict::TooRecent { wait } => {
+                wait.map(|w| w.as_secs())
+            }
+            _ => None,
+        };
+
+        Self {
+            part,
+            answer: answer.to_string(),
+            feedback: Feedback::from(&submission.verdict),
+            submitted_at,
+            wait,
+        }
+    }
+}
+
+/// Why a submission was refused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect(String),
+    KnownWrong,
+    /// The answer is at or above an answer that was too high.
+    AboveBound(String),
+    /// The answer is at or below an answer that was too low.
+    BelowBound(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Refusal::AboveBound(bound) => {
+                write!(f, "`{bound}` was too high, this answer can't be right.")
+            }
+            Refusal::BelowBound(bound) => {
+                write!(f, "`{bound}` was too low, this answer can't be right.")
+            }
+            Refusal::Cooldown(wait) => {
+                write!(
+                    f,
+                    "the website asked to wait, try again in {}s.",
+                    wait.as_secs()
+                )
+            }
+        }
+    }
+}
+
+/// All submissions of a single day, stored in `data/submissions/<day>.json`
+/// (`data/<year>/submissions/<day>.json` for namespaced years).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub entries: Vec<Entry>,
+}
+
+fn get_log_path(year: Option<Year>, day: Day) -> String {
+    format!("data/{}/{day}.json", data_folder(year, "submissions"))
+}
+
+/// The current time as unix timestamp in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl SubmissionLog {
+    /// Read the submission log of a day. If not present, returns an empty log.
+    pub fn read(year: Option<Year>, day: Day) -> Self {
+        match fs::read_to_string(get_log_path(year, day)) {
+            Ok(s) => SubmissionLog::try_from(s).unwrap_or_else(|e| {
+                eprintln!("{}: {e}", get_log_path(year, day));
+                SubmissionLog::default()
+            }),
+            Err(_) => SubmissionLog::default(),
+        }
+    }
+
+    /// Write the submission log of a day to disk.
+    pub fn store(&self, year: Option<Year>, day: Day) -> Result<(), io::Error> {
+        fs::create_dir_all(format!("data/{}", data_folder(year, "submissions")))?;
+        let mut file = fs::File::create(get_log_path(year, day))?;
+        JsonValue::from(self).format_to(&mut file)
+    }
+
+    /// Append a single entry to the log of a day.
+    pub fn append(year: Option<Year>, day: Day, entry: Entry) -> Result<(), io::Error> {
+        let mut log = SubmissionLog::read(year, day);
+        log.entries.push(entry);
+        log.store(year, day)
+    }
+
+    /// Check whether submitting `answer` for `part` at time `now` could succeed.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(wait) = self
+            .entries
+            .iter()
+            .filter_map(|e| Some(e.submitted_at + e.wait?))
+            .max()
+            .filter(|until| *until > now)
+            .map(|until| until - now)
+        {
+            return Err(Refusal::Cooldown(Duration::from_secs(wait)));
+        }
+
+        let entries = || self.entries.iter().filter(|e| e.part == part);
+
+        if let Some(entry) = entries().find(|e| e.feedback == Feedback::Correct) {
+            return Err(Refusal::AlreadyCorrect(entry.answer.clone()));
+        }
+
+        if entries().any(|e| e.answer == answer && e.feedback.is_wrong()) {
+            return Err(Refusal::KnownWrong);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |feedback: Feedback| {
+            entries()
+                .filter(move |e| e.feedback == feedback)
+                .filter_map(|e| Some((e.answer.parse::<i128>().ok()?, &e.answer)))
+        };
+
+        if let Some((_, answer)) = bound(Feedback::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min()
+        {
+            return Err(Refusal::AboveBound(answer.clone()));
+        }
+
+        if let Some((_, answer)) = bound(Feedback::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max()
+        {
+            return Err(Refusal::BelowBound(answer.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "feedback".into(),
+            JsonValue::String(value.feedback.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "submitted_at".into(),
+            JsonValue::Number(value.submitted_at as f64),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wait".into(),
+            value
+                .wait
+                .map_or(JsonValue::Null, |w| JsonValue::Number(w as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Entry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected entries to be objects.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("expected entry.{key} to be a number."))
+        };
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("expected entry.{key} to be a string."))
+        };
+
+        let wait = match json.get("wait") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(_) => Some(number("wait")? as u64),
+        };
+
+        Ok(Entry {
+            part: number("part")? as u8,
+            answer: string("answer")?,
+            feedback: string("feedback")?.parse()?,
+            submitted_at: number("submitted_at")? as u64,
+            wait,
+        })
+    }
+}
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "entries".into(),
+            JsonValue::Array(value.entries.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let entries = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("entries")
+            .ok_or("expected JSON document to contain key `entries`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `entries` to be an array.")?;
+
+        Ok(SubmissionLog {
+            entries: entries
+                .iter()
+                .map(Entry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Entry, Feedback, Refusal, SubmissionLog};
+
+    fn entry(part: u8, answer: &str, feedback: Feedback, submitted_at: u64) -> Entry {
+        Entry {
+            part,
+            answer: answer.into(),
+            feedback,
+            submitted_at,
+            wait: Some(60),
+        }
+    }
+
+    fn get_mock_log() -> SubmissionLog {
+        SubmissionLog {
+            entries: vec![
+                entry(1, "100", Feedback::TooHigh, 0),
+                entry(1, "10", Feedback::TooLow, 100),
+                entry(1, "50", Feedback::Incorrect, 200),
+                entry(2, "abc", Feedback::Correct, 300),
+            ],
+        }
+    }
+
+    #[test]
+    fn allows_plausible_answers() {
+        let log = get_mock_log();
+        assert_eq!(log.check(1, "42", 1000), Ok(()));
+        assert_eq!(log.check(1, "xyz", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = get_mock_log();
+        assert_eq!(log.check(1, "50", 1000), Err(Refusal::KnownWrong));
+        assert_eq!(
+            log.check(2, "abd", 1000),
+            Err(Refusal::AlreadyCorrect("abc".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(1, "101", 1000),
+            Err(Refusal::AboveBound("100".into()))
+        );
+        assert_eq!(
+            log.check(1, "-3", 1000),
+            Err(Refusal::BelowBound("10".into()))
+        );
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(1, "42", 320),
+            Err(Refusal::Cooldown(Duration::from_secs(40)))
+        );
+    }
+
+    #[test]
+    fn round_trips_log() {
+        let mut log = get_mock_log();
+        log.entries[0].wait = None;
+        let json = JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+}
@@ -1,17 +1,73 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::record::{Measurement, PartRecord, PartStatus, PARSE_PHASE};
+use crate::template::{data_folder, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// The timings of the default year are stored in `data/timings.json`,
+/// those of namespaced years in `data/<year>/timings.json`.
+fn get_timings_path(year: Option<Year>) -> String {
+    format!("./data/{}", data_folder(year, "timings.json"))
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Only present for days with a parse phase.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Measurement>,
+    pub part_1_stats: Option<Measurement>,
+    pub part_2_stats: Option<Measurement>,
     pub total_nanos: f64,
+    /// Total nanos of the earlier stored runs of the day, oldest first.
+    pub history: Vec<f64>,
+}
+
+impl Timing {
+    /// Collect the timings of a day from the records of its parse phase and solved parts.
+    pub fn from_records(day: Day, records: &[PartRecord]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0_f64,
+            history: vec![],
+        };
+
+        // the median is used for display, as it is robust against slow samples.
+        for record in records.iter().filter(|r| r.status == PartStatus::Solved) {
+            let timing_str = Some(format!("{:.1?}", record.time.median));
+
+            match record.part {
+                PARSE_PHASE => {
+                    timing.parse = timing_str;
+                    timing.parse_stats = Some(record.time);
+                }
+                1 => {
+                    timing.part_1 = timing_str;
+                    timing.part_1_stats = Some(record.time);
+                }
+                2 => {
+                    timing.part_2 = timing_str;
+                    timing.part_2_stats = Some(record.time);
+                }
+                _ => continue,
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            let nanos = record.time.median.as_nanos() as f64;
+            timing.total_nanos += nanos;
+        }
+
+        timing
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -23,19 +79,16 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(format!("./data/{}", data_folder(year, "")))?;
+        let mut file = fs::File::create(get_timings_path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        match Timings::read_from_path(&get_timings_path(year)) {
             Ok(timings) => timings,
             Err(e) => {
                 eprintln!("{e}");
@@ -44,12 +97,28 @@ impl Timings {
         }
     }
 
+    /// Rehydrate timings from the JSON file at `path`, e.g. a snapshot of earlier timings.
+    pub fn read_from_path(path: &str) -> Result<Self, String> {
+        fs::read_to_string(path)
+            .map_err(|x| format!("{path}: {x}"))
+            .and_then(Timings::try_from)
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// The totals of overwritten days are appended to their history, which keeps at most
+    /// `history` entries.
+    pub fn merge(&self, new: &Self, history: usize) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.history.clone_from(&old.history);
+                timing.history.push(old.total_nanos);
+            }
+            let excess = timing.history.len().saturating_sub(history);
+            timing.history.drain(..excess);
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +127,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -124,6 +193,14 @@ impl From<&Timing> for JsonValue {
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -140,6 +217,29 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                stats.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        map.insert(
+            "history_nanos".into(),
+            JsonValue::Array(
+                value
+                    .history
+                    .iter()
+                    .copied()
+                    .map(JsonValue::Number)
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -168,16 +268,45 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // only days with a parse phase have a parse timing.
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // statistics are optional, timings stored by older versions do not have them.
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => Measurement::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        // like statistics, the history is missing from timings stored by older versions.
+        let history = match json.get("history_nanos") {
+            Some(v) if !v.is_null() => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|v| v.iter().map(|x| x.get::<f64>().copied()).collect())
+                .ok_or("Expected timing.history_nanos to be an array of numbers.")?,
+            _ => vec![],
+        };
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats: stats("parse_stats")?,
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
             total_nanos,
+            history,
         })
     }
 }
@@ -195,26 +324,104 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
+                    history: vec![],
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 4e+10,
+                    history: vec![],
                 },
             ],
         }
     }
 
+    mod from_records {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                record::{Measurement, PartRecord},
+                timings::Timing,
+            },
+        };
+
+        fn get_mock_record(part: u8, answer: Option<&str>, nanos: u64) -> PartRecord {
+            PartRecord::new(
+                day!(1),
+                part,
+                answer.as_ref(),
+                Measurement {
+                    samples: 10,
+                    ..Measurement::single(Duration::from_nanos(nanos))
+                },
+            )
+        }
+
+        #[test]
+        fn collects_execution_times() {
+            let res = Timing::from_records(
+                day!(1),
+                &[
+                    get_mock_record(1, Some("0"), 74),
+                    get_mock_record(2, Some("10"), 74_130_000),
+                ],
+            );
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let parse = PartRecord::parse_phase(
+                day!(1),
+                &Ok(()),
+                Measurement::single(Duration::from_nanos(26)),
+            );
+            let res = Timing::from_records(day!(1), &[parse, get_mock_record(1, Some("0"), 74)]);
+            assert_eq!(res.total_nanos, 100_f64);
+            assert_eq!(res.parse.unwrap(), "26.0ns");
+            assert_eq!(res.parse_stats.unwrap().samples, 1);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+        }
+
+        #[test]
+        fn skips_unsolved_parts() {
+            let res = Timing::from_records(
+                day!(1),
+                &[get_mock_record(1, None, 50), get_mock_record(2, None, 50)],
+            );
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+    }
+
     mod deserialization {
         use crate::{day, template::timings::Timings};
 
@@ -230,6 +437,28 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_history() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "history_nanos": [3000000000, 2000000000] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].history, vec![3e9, 2e9]);
+
+            let json = tinyjson::JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].history, vec![3e9, 2e9]);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "nanos": 1100000, "median_nanos": 1000000, "min_nanos": 900000, "max_nanos": 1500000, "stddev_nanos": 50000, "p95_nanos": 1400000, "samples": 100, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.median.as_nanos(), 1_000_000);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -286,13 +515,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3_000_000_000_f64,
+                    history: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +534,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 1_000_000_000_f64,
+                    history: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -314,13 +553,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0.0,
+                    history: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -338,12 +582,17 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
+                    history: vec![],
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, 10);
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -358,12 +607,17 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
+                    history: vec![],
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, 10);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
@@ -376,7 +630,7 @@ mod tests {
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, 10);
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -384,8 +638,23 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, 10);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn appends_history_of_overwritten_days() {
+            let mut timings = get_mock_timings();
+            timings.data[1].history = vec![1e10, 2e10];
+            let mut other = get_mock_timings();
+            other.data.truncate(2);
+            other.data[1].total_nanos = 5e10;
+
+            let merged = timings.merge(&other, 2);
+            assert_eq!(merged.data[0].history, vec![3e10]);
+            assert_eq!(merged.data[1].history, vec![2e10, 7e10]);
+            assert_eq!(merged.data[1].total_nanos, 5e10);
+            assert!(merged.data[2].history.is_empty());
+        }
     }
 }
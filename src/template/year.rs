@@ -0,0 +1,138 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// A year in which an advent of code event took place (i.e. 2015 or later).
+///
+/// The year set in `AOC_YEAR` is the workspace's default event, its files live directly in
+/// `data/` and `src/bin`. Other years are namespaced, see [`data_folder`] and [`bin_name`].
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2019).unwrap();
+/// assert_eq!(year.to_string(), "2019")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a valid event year,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Reads the workspace's default year from `AOC_YEAR`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Returns the namespace of a year: [`None`] if it is the workspace's default year.
+    pub fn namespace(year: Option<Year>) -> Option<Year> {
+        year.filter(|year| Some(*year) != Year::from_env())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The folder inside `data/` that holds files of a year, e.g. `inputs` or `2019/inputs`.
+pub fn data_folder(year: Option<Year>, folder: &str) -> String {
+    match year {
+        Some(year) => format!("{year}/{folder}"),
+        None => folder.to_string(),
+    }
+}
+
+/// The name of a day's solution binary, e.g. `01` or `2019-01`.
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bin_name, data_folder, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2019".parse::<Year>().unwrap(), Year(2019));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("19".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn namespaces_paths() {
+        assert_eq!(data_folder(None, "inputs"), "inputs");
+        assert_eq!(data_folder(Some(Year(2019)), "inputs"), "2019/inputs");
+        assert_eq!(bin_name(None, day!(1)), "01");
+        assert_eq!(bin_name(Some(Year(2019)), day!(1)), "2019-01");
+    }
+}
@@ -0,0 +1,478 @@
+use std::{iter, ops::RangeInclusive};
+
+use num_traits::PrimInt;
+
+use super::point::{Pt, Pt3};
+
+/// All values from `from` to `to`, both included.
+pub(super) fn inclusive<T: PrimInt>(from: T, to: T) -> impl Iterator<Item = T> {
+    iter::successors((from <= to).then_some(from), move |&v| {
+        (v < to).then(|| v + T::one())
+    })
+}
+
+/// An axis-aligned rectangle of grid points, from `min` to `max` inclusive.
+/// `min` must not be greater than `max` on either axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Pt<T>,
+    pub max: Pt<T>,
+}
+
+impl<T: PrimInt> Rect<T> {
+    /// The rectangle from `min` to `max`, or `None` if `min` is greater on either axis.
+    pub fn new(min: Pt<T>, max: Pt<T>) -> Option<Self> {
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    /// The rectangle of `width * height` points whose smallest corner is `min`,
+    /// or `None` if it would be empty.
+    pub fn from_size(min: Pt<T>, width: T, height: T) -> Option<Self> {
+        (!width.is_zero() && !height.is_zero()).then(|| Self {
+            min,
+            max: Pt {
+                x: min.x + width - T::one(),
+                y: min.y + height - T::one(),
+            },
+        })
+    }
+
+    /// The smallest rectangle containing all of `pts`, or `None` if there are none.
+    pub fn bounding(pts: impl IntoIterator<Item = Pt<T>>) -> Option<Self> {
+        Pt::bounding_box(pts).map(|(min, max)| Self { min, max })
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::one()
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::one()
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, pt: Pt<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&pt.x) && (self.min.y..=self.max.y).contains(&pt.y)
+    }
+
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn intersects(&self, other: &Rect<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Rect<T>) -> Option<Self> {
+        let min = Pt {
+            x: self.min.x.max(other.min.x),
+            y: self.min.y.max(other.min.y),
+        };
+        let max = Pt {
+            x: self.max.x.min(other.max.x),
+            y: self.max.y.min(other.max.y),
+        };
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    /// The smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect<T>) -> Self {
+        Self {
+            min: Pt {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+            },
+            max: Pt {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+            },
+        }
+    }
+
+    /// All points of the rectangle, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Pt<T>> {
+        let (min, max) = (self.min, self.max);
+        inclusive(min.y, max.y).flat_map(move |y| inclusive(min.x, max.x).map(move |x| Pt { x, y }))
+    }
+}
+
+/// An axis-aligned cuboid of points in space, from `min` to `max` inclusive.
+/// `min` must not be greater than `max` on any axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T> {
+    pub min: Pt3<T>,
+    pub max: Pt3<T>,
+}
+
+impl<T: PrimInt> Cuboid<T> {
+    /// The cuboid from `min` to `max`, or `None` if `min` is greater on any axis.
+    pub fn new(min: Pt3<T>, max: Pt3<T>) -> Option<Self> {
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    /// The cuboid of `width * height * depth` points whose smallest corner is `min`,
+    /// or `None` if it would be empty.
+    pub fn from_size(min: Pt3<T>, width: T, height: T, depth: T) -> Option<Self> {
+        (!width.is_zero() && !height.is_zero() && !depth.is_zero()).then(|| Self {
+            min,
+            max: Pt3 {
+                x: min.x + width - T::one(),
+                y: min.y + height - T::one(),
+                z: min.z + depth - T::one(),
+            },
+        })
+    }
+
+    /// The smallest cuboid containing all of `pts`, or `None` if there are none.
+    pub fn bounding(pts: impl IntoIterator<Item = Pt3<T>>) -> Option<Self> {
+        Pt3::bounding_box(pts).map(|(min, max)| Self { min, max })
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::one()
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::one()
+    }
+
+    pub fn depth(&self) -> T {
+        self.max.z - self.min.z + T::one()
+    }
+
+    pub fn volume(&self) -> T {
+        self.width() * self.height() * self.depth()
+    }
+
+    pub fn contains(&self, pt: Pt3<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&pt.x)
+            && (self.min.y..=self.max.y).contains(&pt.y)
+            && (self.min.z..=self.max.z).contains(&pt.z)
+    }
+
+    pub fn contains_cuboid(&self, other: &Cuboid<T>) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn intersects(&self, other: &Cuboid<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Cuboid<T>) -> Option<Self> {
+        let min = Pt3 {
+            x: self.min.x.max(other.min.x),
+            y: self.min.y.max(other.min.y),
+            z: self.min.z.max(other.min.z),
+        };
+        let max = Pt3 {
+            x: self.max.x.min(other.max.x),
+            y: self.max.y.min(other.max.y),
+            z: self.max.z.min(other.max.z),
+        };
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Self { min, max })
+    }
+
+    /// The smallest cuboid containing both cuboids.
+    pub fn union(&self, other: &Cuboid<T>) -> Self {
+        Self {
+            min: Pt3 {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+                z: self.min.z.min(other.min.z),
+            },
+            max: Pt3 {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+                z: self.max.z.max(other.max.z),
+            },
+        }
+    }
+
+    /// All points of the cuboid, ordered by z, then y, then x.
+    pub fn points(&self) -> impl Iterator<Item = Pt3<T>> {
+        let (min, max) = (self.min, self.max);
+        inclusive(min.z, max.z).flat_map(move |z| {
+            inclusive(min.y, max.y)
+                .flat_map(move |y| inclusive(min.x, max.x).map(move |x| Pt3 { x, y, z }))
+        })
+    }
+}
+
+/// A set of integers, stored as sorted inclusive ranges. Overlapping and adjacent ranges are
+/// merged on insertion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // Ranges that end right before `start` or begin right after `end` are merged as well.
+        let from = self
+            .ranges
+            .partition_point(|r| r.1.saturating_add(T::one()) < start);
+        let to = self
+            .ranges
+            .partition_point(|r| r.0 <= end.saturating_add(T::one()));
+        if from < to {
+            start = start.min(self.ranges[from].0);
+            end = end.max(self.ranges[to - 1].1);
+        }
+        self.ranges.splice(from..to, [(start, end)]);
+    }
+
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let from = self.ranges.partition_point(|r| r.1 < start);
+        let to = self.ranges.partition_point(|r| r.0 <= end);
+        if from == to {
+            return;
+        }
+        let (first, last) = (self.ranges[from].0, self.ranges[to - 1].1);
+        let mut rest = Vec::with_capacity(2);
+        if first < start {
+            rest.push((first, start - T::one()));
+        }
+        if last > end {
+            rest.push((end + T::one(), last));
+        }
+        self.ranges.splice(from..to, rest);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.1 < value);
+        self.ranges.get(i).is_some_and(|r| r.0 <= value)
+    }
+
+    /// The number of values in the set. Overflows if the set holds every value of `T`.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |sum, r| sum + r.1 - r.0 + T::one())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The merged ranges, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+}
+
+impl<T: PrimInt> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cuboid, IntervalSet, Rect};
+    use crate::util::point::{Pt, Pt3};
+
+    fn rect(min: (u32, u32), max: (u32, u32)) -> Rect<u32> {
+        Rect::new(min.into(), max.into()).unwrap()
+    }
+
+    fn ranges<T: Copy>(set: &IntervalSet<T>) -> Vec<(T, T)> {
+        set.ranges.clone()
+    }
+
+    #[test]
+    fn creates_rects() {
+        assert_eq!(
+            Rect::from_size(Pt { x: 1u32, y: 2 }, 3, 4),
+            Some(rect((1, 2), (3, 5)))
+        );
+        assert_eq!(Rect::from_size(Pt { x: 1u32, y: 2 }, 0, 4), None);
+        assert_eq!(Rect::new(Pt { x: 2u32, y: 0 }, Pt { x: 1, y: 5 }), None);
+        let r = rect((1, 2), (3, 5));
+        assert_eq!((r.width(), r.height(), r.area()), (3, 4, 12));
+        assert_eq!(rect((4, 4), (4, 4)).area(), 1);
+        assert_eq!(
+            Rect::bounding([Pt { x: 3, y: 0 }, Pt { x: 1, y: 2 }]),
+            Some(rect((1, 0), (3, 2)))
+        );
+        assert_eq!(Rect::<u32>::bounding([]), None);
+    }
+
+    #[test]
+    fn intersects_rects() {
+        let a = rect((1, 3), (4, 6));
+        let b = rect((3, 1), (6, 4));
+        assert_eq!(a.intersection(&b), Some(rect((3, 3), (4, 4))));
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+        assert!(a.intersects(&b));
+        // rects that only share an edge still overlap on its points.
+        assert_eq!(
+            a.intersection(&rect((4, 0), (9, 3))),
+            Some(rect((4, 3), (4, 3)))
+        );
+        assert_eq!(a.intersection(&rect((5, 0), (9, 9))), None);
+        assert!(!a.intersects(&rect((0, 7), (9, 9))));
+    }
+
+    #[test]
+    fn unites_and_contains_rects() {
+        let a = rect((1, 3), (4, 6));
+        let b = rect((3, 1), (6, 4));
+        assert_eq!(a.union(&b), rect((1, 1), (6, 6)));
+        assert!(a.union(&b).contains_rect(&a));
+        assert!(!a.contains_rect(&b));
+        assert!(a.contains(Pt { x: 4, y: 3 }));
+        assert!(!a.contains(Pt { x: 5, y: 3 }));
+        assert!(!a.contains(Pt { x: 1, y: 2 }));
+    }
+
+    #[test]
+    fn iterates_rect_points_in_reading_order() {
+        let pts: Vec<_> = rect((1, 2), (2, 3)).points().collect();
+        assert_eq!(
+            pts,
+            [
+                Pt { x: 1, y: 2 },
+                Pt { x: 2, y: 2 },
+                Pt { x: 1, y: 3 },
+                Pt { x: 2, y: 3 }
+            ]
+        );
+        let edge = Rect::new(Pt { x: 254u8, y: 255 }, Pt { x: 255, y: 255 }).unwrap();
+        assert_eq!(edge.points().count(), 2);
+    }
+
+    #[test]
+    fn handles_cuboids() {
+        let a = Cuboid::from_size(Pt3 { x: 0, y: 0, z: 0 }, 2, 3, 4).unwrap();
+        assert_eq!(
+            (a.width(), a.height(), a.depth(), a.volume()),
+            (2, 3, 4, 24)
+        );
+        assert_eq!(Cuboid::from_size(Pt3 { x: 0, y: 0, z: 0 }, 2, 3, 0), None);
+        assert_eq!(
+            Cuboid::new(Pt3 { x: 0, y: 0, z: 1 }, Pt3 { x: 1, y: 1, z: 0 }),
+            None
+        );
+        let b = Cuboid::new(Pt3 { x: 1, y: 2, z: 3 }, Pt3 { x: 5, y: 5, z: 5 }).unwrap();
+        assert_eq!(
+            a.intersection(&b),
+            Cuboid::new(Pt3 { x: 1, y: 2, z: 3 }, Pt3 { x: 1, y: 2, z: 3 })
+        );
+        assert_eq!(
+            a.union(&b),
+            Cuboid::new(Pt3 { x: 0, y: 0, z: 0 }, Pt3 { x: 5, y: 5, z: 5 }).unwrap()
+        );
+        assert!(a.union(&b).contains_cuboid(&b));
+        assert!(!a.intersects(&Cuboid::from_size(Pt3 { x: 2, y: 0, z: 0 }, 1, 1, 1).unwrap()));
+        let pts: Vec<_> = a.points().take(3).collect();
+        assert_eq!(
+            pts,
+            [
+                Pt3 { x: 0, y: 0, z: 0 },
+                Pt3 { x: 1, y: 0, z: 0 },
+                Pt3 { x: 0, y: 1, z: 0 }
+            ]
+        );
+        assert_eq!(a.points().count(), 24);
+        assert_eq!(Cuboid::bounding(a.points()), Some(a));
+    }
+
+    #[test]
+    fn merges_intervals() {
+        let mut set: IntervalSet<i32> = [5..=7, 1..=3].into_iter().collect();
+        assert_eq!(ranges(&set), [(1, 3), (5, 7)]);
+        // adjacent ranges are merged.
+        set.insert(4..=4);
+        assert_eq!(ranges(&set), [(1, 7)]);
+        set.insert(10..=12);
+        set.insert(20..=20);
+        set.insert(0..=15);
+        assert_eq!(ranges(&set), [(0, 15), (20, 20)]);
+        // empty ranges change nothing.
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(18..=17);
+        assert_eq!(ranges(&set), [(0, 15), (20, 20)]);
+        assert_eq!(set.len(), 17);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0..=15, 20..=20]);
+    }
+
+    #[test]
+    fn merges_intervals_at_the_limits() {
+        let mut set = IntervalSet::<u8>::new();
+        set.insert(250..=255);
+        set.insert(0..=0);
+        set.insert(1..=249);
+        assert_eq!(ranges(&set), [(0, 255)]);
+        let set: IntervalSet<i8> = [-128..=-128, 127..=127].into_iter().collect();
+        assert_eq!(ranges(&set), [(-128, -128), (127, 127)]);
+    }
+
+    #[test]
+    fn removes_intervals() {
+        let mut set: IntervalSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        // splits a range.
+        set.remove(3..=5);
+        assert_eq!(ranges(&set), [(0, 2), (6, 10), (20, 30)]);
+        // trims the ends of the ranges it overlaps.
+        set.remove(8..=25);
+        assert_eq!(ranges(&set), [(0, 2), (6, 7), (26, 30)]);
+        set.remove(12..=14);
+        #[allow(clippy::reversed_empty_ranges)]
+        set.remove(7..=6);
+        assert_eq!(ranges(&set), [(0, 2), (6, 7), (26, 30)]);
+        set.remove(i32::MIN..=i32::MAX);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn removes_intervals_at_the_limits() {
+        let mut set: IntervalSet<u8> = [0..=255].into_iter().collect();
+        set.remove(0..=0);
+        set.remove(255..=255);
+        assert_eq!(ranges(&set), [(1, 254)]);
+        set.remove(0..=1);
+        set.remove(254..=255);
+        assert_eq!(ranges(&set), [(2, 253)]);
+        assert_eq!(set.len(), 252);
+    }
+
+    #[test]
+    fn looks_up_intervals() {
+        let set: IntervalSet<u32> = [2..=4, 8..=8].into_iter().collect();
+        let contained: Vec<u32> = (0..10).filter(|&v| set.contains(v)).collect();
+        assert_eq!(contained, [2, 3, 4, 8]);
+        assert!(!IntervalSet::<u32>::new().contains(0));
+        assert!(IntervalSet::<u32>::new().is_empty());
+    }
+}
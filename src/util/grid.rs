@@ -0,0 +1,533 @@
+use std::{
+    collections::{hash_map, HashMap},
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use num_traits::PrimInt;
+
+use super::{
+    geom::inclusive,
+    point::{Dir4, Dir8, Pt},
+};
+
+/// A dense grid of `width * height` cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pt<usize>) -> T) -> Self {
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| Pt { x, y }))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    /// Parses a char map, converting every char and its point with `f`.
+    /// All lines must have the same length.
+    pub fn parse(
+        s: &str,
+        mut f: impl FnMut(Pt<usize>, char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in s.lines() {
+            let len = line.chars().count();
+            let expected = *width.get_or_insert(len);
+            if len != expected {
+                return Err(anyhow!(
+                    "line {} of the grid is not {expected} wide",
+                    height + 1
+                ));
+            }
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(Pt { x, y: height }, c)?);
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pt: Pt<usize>) -> bool {
+        pt.x < self.width && pt.y < self.height
+    }
+
+    pub fn get(&self, pt: Pt<usize>) -> Option<&T> {
+        self.contains(pt)
+            .then(|| &self.cells[pt.y * self.width + pt.x])
+    }
+
+    pub fn get_mut(&mut self, pt: Pt<usize>) -> Option<&mut T> {
+        self.contains(pt)
+            .then(|| &mut self.cells[pt.y * self.width + pt.x])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Pt<usize>> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pt { x, y }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pt<usize>, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The up to 4 orthogonal neighbors of `pt` that lie within the grid, in reading order.
+    pub fn neighbors4(&self, pt: Pt<usize>) -> impl Iterator<Item = Pt<usize>> + '_ {
+        Dir4::iter()
+            .filter_map(move |dir| pt.checked_step(dir))
+            .filter(|&p| self.contains(p))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbors of `pt` that lie within the grid,
+    /// in reading order.
+    pub fn neighbors8(&self, pt: Pt<usize>) -> impl Iterator<Item = Pt<usize>> + '_ {
+        Dir8::iter()
+            .filter_map(move |dir| pt.checked_step(dir))
+            .filter(|&p| self.contains(p))
+    }
+
+    /// Renders the grid back to a char map, converting every cell and its point with `f`.
+    /// Every row ends with a newline, even if the grid has a width of 0.
+    pub fn render(&self, mut f: impl FnMut(Pt<usize>, &T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for (y, row) in self.rows().enumerate() {
+            s.extend(row.iter().enumerate().map(|(x, cell)| f(Pt { x, y }, cell)));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T> Index<Pt<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Pt<usize>) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic!("{index:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pt<usize>> for Grid<T> {
+    fn index_mut(&mut self, index: Pt<usize>) -> &mut Self::Output {
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("{index:?} is out of bounds"))
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+    <T as TryFrom<char>>::Error: Into<anyhow::Error>,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |_, c| T::try_from(c).map_err(Into::into))
+    }
+}
+
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// A grid that only stores the cells that are set, e.g. for grids without known bounds.
+/// Points are `Pt<C>`, so that signed coordinates can grow in every direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T, C: Eq + Hash = usize> {
+    cells: HashMap<Pt<C>, T>,
+}
+
+impl<T, C: Eq + Hash> Default for SparseGrid<T, C> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Parses a char map, converting every char and its point with `f`.
+    /// Chars for which `f` returns `None` are left empty.
+    pub fn parse(
+        s: &str,
+        mut f: impl FnMut(Pt<usize>, char) -> anyhow::Result<Option<T>>,
+    ) -> anyhow::Result<Self> {
+        let mut cells = HashMap::new();
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let pt = Pt { x, y };
+                if let Some(value) = f(pt, c)? {
+                    cells.insert(pt, value);
+                }
+            }
+        }
+        Ok(Self { cells })
+    }
+}
+
+impl<T, C> SparseGrid<T, C>
+where
+    C: PrimInt + Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pt: Pt<C>) -> bool {
+        self.cells.contains_key(&pt)
+    }
+
+    pub fn get(&self, pt: Pt<C>) -> Option<&T> {
+        self.cells.get(&pt)
+    }
+
+    pub fn get_mut(&mut self, pt: Pt<C>) -> Option<&mut T> {
+        self.cells.get_mut(&pt)
+    }
+
+    pub fn insert(&mut self, pt: Pt<C>, value: T) -> Option<T> {
+        self.cells.insert(pt, value)
+    }
+
+    pub fn remove(&mut self, pt: Pt<C>) -> Option<T> {
+        self.cells.remove(&pt)
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(Pt<C>, &mut T) -> bool) {
+        self.cells.retain(|&pt, value| f(pt, value));
+    }
+
+    pub fn entry(&mut self, pt: Pt<C>) -> hash_map::Entry<'_, Pt<C>, T> {
+        self.cells.entry(pt)
+    }
+
+    /// The set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pt<C>, &T)> {
+        self.cells.iter().map(|(&pt, value)| (pt, value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// The smallest and the largest corner of the box around all set cells.
+    pub fn bounds(&self) -> Option<(Pt<C>, Pt<C>)> {
+        Pt::bounding_box(self.cells.keys().copied())
+    }
+
+    /// The cells of row `y` between `min_x` and `max_x`, including empty ones.
+    pub fn row(&self, y: C, min_x: C, max_x: C) -> impl Iterator<Item = Option<&T>> + '_ {
+        inclusive(min_x, max_x).map(move |x| self.get(Pt { x, y }))
+    }
+
+    /// The cells of column `x` between `min_y` and `max_y`, including empty ones.
+    pub fn column(&self, x: C, min_y: C, max_y: C) -> impl Iterator<Item = Option<&T>> + '_ {
+        inclusive(min_y, max_y).map(move |y| self.get(Pt { x, y }))
+    }
+
+    /// The up to 4 orthogonal neighbors of `pt` that are set, in reading order.
+    pub fn neighbors4(&self, pt: Pt<C>) -> impl Iterator<Item = (Pt<C>, &T)> + '_ {
+        Dir4::iter()
+            .filter_map(move |dir| pt.checked_step(dir))
+            .filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbors of `pt` that are set, in reading order.
+    pub fn neighbors8(&self, pt: Pt<C>) -> impl Iterator<Item = (Pt<C>, &T)> + '_ {
+        Dir8::iter()
+            .filter_map(move |dir| pt.checked_step(dir))
+            .filter_map(|p| Some((p, self.get(p)?)))
+    }
+
+    /// Renders the box around all set cells to a char map, converting every cell and its point
+    /// with `f`.
+    pub fn render(&self, mut f: impl FnMut(Pt<C>, Option<&T>) -> char) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+        let mut s = String::new();
+        for y in inclusive(min.y, max.y) {
+            s.extend(inclusive(min.x, max.x).map(|x| {
+                let pt = Pt { x, y };
+                f(pt, self.get(pt))
+            }));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T, C> Index<Pt<C>> for SparseGrid<T, C>
+where
+    C: PrimInt + Hash + Debug,
+{
+    type Output = T;
+
+    fn index(&self, index: Pt<C>) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic!("{index:?} is not set"))
+    }
+}
+
+impl<T, C> IndexMut<Pt<C>> for SparseGrid<T, C>
+where
+    C: PrimInt + Hash + Debug,
+{
+    fn index_mut(&mut self, index: Pt<C>) -> &mut Self::Output {
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("{index:?} is not set"))
+    }
+}
+
+impl<T, C> FromIterator<(Pt<C>, T)> for SparseGrid<T, C>
+where
+    C: PrimInt + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (Pt<C>, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, SparseGrid};
+    use crate::util::point::Pt;
+
+    const MAP: &str = "#.#\n..#\n";
+
+    fn grid() -> Grid<char> {
+        MAP.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pt { x: 2, y: 1 }], '#');
+        assert_eq!(grid.get(Pt { x: 3, y: 0 }), None);
+        assert_eq!(grid.to_string(), MAP);
+        assert_eq!(grid.render(|_, &c| c), MAP);
+    }
+
+    #[test]
+    fn rejects_uneven_rows() {
+        let err = "#.#\n.#\n".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(err.to_string(), "line 2 of the grid is not 3 wide");
+    }
+
+    #[test]
+    fn renders_with_points() {
+        let rendered = grid().render(|pt, &c| if pt == (Pt { x: 1, y: 0 }) { '@' } else { c });
+        assert_eq!(rendered, "#@#\n..#\n");
+    }
+
+    #[test]
+    fn renders_empty_rows() {
+        let grid = Grid::new(0, 2, '.');
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.to_string(), "\n\n");
+        assert_eq!(grid.render(|_, &c| c), "\n\n");
+    }
+
+    #[test]
+    fn reads_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['.', '.', '#']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).collect::<String>(), "##");
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, ["#.", "..", "##"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is out of bounds")]
+    fn rejects_rows_out_of_bounds() {
+        grid().row(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is out of bounds")]
+    fn rejects_columns_out_of_bounds() {
+        grid().column(3);
+    }
+
+    #[test]
+    fn finds_neighbors_in_reading_order() {
+        let grid = Grid::new(3, 3, 0);
+        let center = Pt { x: 1, y: 1 };
+        let pts = |pts: &[(usize, usize)]| pts.iter().map(|&p| Pt::from(p)).collect::<Vec<_>>();
+        assert_eq!(
+            grid.neighbors4(center).collect::<Vec<_>>(),
+            pts(&[(1, 0), (0, 1), (2, 1), (1, 2)])
+        );
+        assert_eq!(
+            grid.neighbors8(center).collect::<Vec<_>>(),
+            pts(&[
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (0, 1),
+                (2, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2)
+            ])
+        );
+        assert_eq!(
+            grid.neighbors4(Pt { x: 0, y: 0 }).collect::<Vec<_>>(),
+            pts(&[(1, 0), (0, 1)])
+        );
+        assert_eq!(
+            grid.neighbors8(Pt { x: 2, y: 2 }).collect::<Vec<_>>(),
+            pts(&[(1, 1), (2, 1), (1, 2)])
+        );
+    }
+
+    fn sparse() -> SparseGrid<char> {
+        SparseGrid::parse("....\n.#..\n...#\n", |_, c| Ok((c == '#').then_some(c))).unwrap()
+    }
+
+    #[test]
+    fn parses_sparse_grids() {
+        let grid = sparse();
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Pt { x: 3, y: 2 }));
+        assert!(!grid.contains(Pt { x: 0, y: 0 }));
+        assert_eq!(grid.bounds(), Some((Pt { x: 1, y: 1 }, Pt { x: 3, y: 2 })));
+        assert_eq!(SparseGrid::<char>::new().bounds(), None);
+    }
+
+    #[test]
+    fn reads_sparse_rows_and_columns() {
+        let grid = sparse();
+        assert_eq!(
+            grid.row(1, 0, 2).collect::<Vec<_>>(),
+            [None, Some(&'#'), None]
+        );
+        assert_eq!(grid.column(3, 1, 2).collect::<Vec<_>>(), [None, Some(&'#')]);
+    }
+
+    #[test]
+    fn renders_sparse_bounds() {
+        let grid = sparse();
+        assert_eq!(grid.render(|_, c| *c.unwrap_or(&'.')), "#..\n..#\n");
+        assert_eq!(SparseGrid::<char>::new().render(|_, _| '.'), "");
+    }
+
+    #[test]
+    fn finds_set_neighbors() {
+        let grid: SparseGrid<u8> = [(Pt { x: 0, y: 0 }, 1), (Pt { x: 1, y: 1 }, 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            grid.neighbors4(Pt { x: 1, y: 0 }).collect::<Vec<_>>(),
+            [(Pt { x: 0, y: 0 }, &1), (Pt { x: 1, y: 1 }, &2)]
+        );
+        assert_eq!(grid.neighbors8(Pt { x: 0, y: 1 }).count(), 2);
+        assert_eq!(grid.neighbors4(Pt { x: 0, y: 1 }).count(), 2);
+        assert_eq!(grid.neighbors4(Pt { x: 2, y: 2 }).count(), 0);
+    }
+
+    #[test]
+    fn grows_sparse_grids_into_negative_coordinates() {
+        let mut grid: SparseGrid<char, i32> = SparseGrid::new();
+        grid.insert(Pt { x: -1, y: -2 }, '#');
+        grid.insert(Pt { x: 1, y: 0 }, '#');
+        assert_eq!(
+            grid.bounds(),
+            Some((Pt { x: -1, y: -2 }, Pt { x: 1, y: 0 }))
+        );
+        assert_eq!(grid.render(|_, c| *c.unwrap_or(&'.')), "#..\n...\n..#\n");
+        assert_eq!(
+            grid.neighbors8(Pt { x: 0, y: -1 }).collect::<Vec<_>>(),
+            [(Pt { x: -1, y: -2 }, &'#'), (Pt { x: 1, y: 0 }, &'#')]
+        );
+        assert_eq!(
+            grid.row(0, -1, 1).collect::<Vec<_>>(),
+            [None, None, Some(&'#')]
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod geom;
 pub mod graph;
+pub mod grid;
 pub mod point;
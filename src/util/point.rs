@@ -1,17 +1,82 @@
 use std::{
+    array,
+    cmp::Ordering,
     fmt::Display,
-    ops::{Add, AddAssign, Div, Mul, MulAssign, Sub},
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
     str::FromStr,
 };
 
 use anyhow::anyhow;
+use num_traits::{AsPrimitive, CheckedAdd, CheckedSub, One, Zero};
 
+fn abs_diff<T>(a: T, b: T) -> T
+where
+    T: Ord + Sub<Output = T>,
+{
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// A point on a grid whose y axis points down. Points are ordered in reading order,
+/// i.e. top to bottom, then left to right.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Pt<T> {
     pub x: T,
     pub y: T,
 }
 
+impl<T> Pt<T>
+where
+    T: Ord + Add<Output = T> + Sub<Output = T> + Copy,
+{
+    pub fn manhattan(&self, other: &Pt<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev(&self, other: &Pt<T>) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+
+    /// The smallest and the largest corner of the box around `pts`, or `None` if there are none.
+    pub fn bounding_box(pts: impl IntoIterator<Item = Pt<T>>) -> Option<(Pt<T>, Pt<T>)> {
+        let mut pts = pts.into_iter();
+        let first = pts.next()?;
+        Some(pts.fold((first, first), |(min, max), pt| {
+            (
+                Pt {
+                    x: min.x.min(pt.x),
+                    y: min.y.min(pt.y),
+                },
+                Pt {
+                    x: max.x.max(pt.x),
+                    y: max.y.max(pt.y),
+                },
+            )
+        }))
+    }
+}
+
+impl<T> PartialOrd for Pt<T>
+where
+    T: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Pt<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y.cmp(&other.y).then_with(|| self.x.cmp(&other.x))
+    }
+}
+
 impl<T> Add<Pt<T>> for Pt<T>
 where
     T: Add<Output = T>,
@@ -36,6 +101,92 @@ where
     }
 }
 
+impl<T> Sub<Pt<T>> for Pt<T>
+where
+    T: Sub<Output = T>,
+{
+    type Output = Pt<T>;
+
+    fn sub(self, rhs: Pt<T>) -> Self::Output {
+        Pt {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T> SubAssign<Pt<T>> for Pt<T>
+where
+    T: SubAssign<T>,
+{
+    fn sub_assign(&mut self, rhs: Pt<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> Neg for Pt<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Pt<T>;
+
+    fn neg(self) -> Self::Output {
+        Pt {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T> Mul<T> for Pt<T>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Pt<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Pt {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T> MulAssign<T> for Pt<T>
+where
+    T: MulAssign<T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T> Div<T> for Pt<T>
+where
+    T: Div<Output = T> + Copy,
+{
+    type Output = Pt<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Pt {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+impl<T> DivAssign<T> for Pt<T>
+where
+    T: DivAssign<T> + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
 impl<T> From<(T, T)> for Pt<T> {
     fn from(value: (T, T)) -> Self {
         Self {
@@ -61,24 +212,96 @@ where
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (x, y) = s
             .trim()
-            .trim_start_matches('(')
-            .trim_end_matches(')')
+            .trim_start_matches(['(', '<'])
+            .trim_end_matches([')', '>'])
             .split_once(',')
             .ok_or(anyhow!("{s} must include a comma to be parsed as Pt"))?;
         Ok(Self {
-            x: x.parse()?,
-            y: y.parse()?,
+            x: x.trim().parse()?,
+            y: y.trim().parse()?,
         })
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+impl<T> Display for Pt<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A point in space. Points are ordered by z, then y, then x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Pt3<T> {
     pub x: T,
     pub y: T,
     pub z: T,
 }
 
+impl<T> Pt3<T>
+where
+    T: Ord + Add<Output = T> + Sub<Output = T> + Copy,
+{
+    pub fn manhattan(&self, other: &Pt3<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(&self, other: &Pt3<T>) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+
+    /// The smallest and the largest corner of the box around `pts`, or `None` if there are none.
+    pub fn bounding_box(pts: impl IntoIterator<Item = Pt3<T>>) -> Option<(Pt3<T>, Pt3<T>)> {
+        let mut pts = pts.into_iter();
+        let first = pts.next()?;
+        Some(pts.fold((first, first), |(min, max), pt| {
+            (
+                Pt3 {
+                    x: min.x.min(pt.x),
+                    y: min.y.min(pt.y),
+                    z: min.z.min(pt.z),
+                },
+                Pt3 {
+                    x: max.x.max(pt.x),
+                    y: max.y.max(pt.y),
+                    z: max.z.max(pt.z),
+                },
+            )
+        }))
+    }
+}
+
+impl Pt3<isize> {
+    pub fn abs_norm(&self, other: &Pt3<isize>) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+impl<T> PartialOrd for Pt3<T>
+where
+    T: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Pt3<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.z
+            .cmp(&other.z)
+            .then_with(|| self.y.cmp(&other.y))
+            .then_with(|| self.x.cmp(&other.x))
+    }
+}
+
 impl<T> From<Pt3<T>> for (T, T, T) {
     fn from(value: Pt3<T>) -> Self {
         (value.x, value.y, value.z)
@@ -110,6 +333,17 @@ where
     }
 }
 
+impl<T> AddAssign for Pt3<T>
+where
+    T: AddAssign<T>,
+{
+    fn add_assign(&mut self, rhs: Pt3<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
 impl<T> Sub for Pt3<T>
 where
     T: Sub<Output = T>,
@@ -125,9 +359,29 @@ where
     }
 }
 
-impl Pt3<isize> {
-    pub fn abs_norm(&self, other: &Pt3<isize>) -> usize {
-        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+impl<T> SubAssign for Pt3<T>
+where
+    T: SubAssign<T>,
+{
+    fn sub_assign(&mut self, rhs: Pt3<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T> Neg for Pt3<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Pt3<T>;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
     }
 }
 
@@ -146,11 +400,11 @@ where
             .split(',')
             .collect::<Vec<_>>()
             .try_into()
-            .unwrap();
+            .map_err(|_| anyhow!("{s} must have three coordinates to be parsed as Pt3"))?;
         Ok(Self {
-            x: arr[0].parse()?,
-            y: arr[1].parse()?,
-            z: arr[2].parse()?,
+            x: arr[0].trim().parse()?,
+            y: arr[1].trim().parse()?,
+            z: arr[2].trim().parse()?,
         })
     }
 }
@@ -169,6 +423,17 @@ where
     }
 }
 
+impl<T> DivAssign<T> for Pt3<T>
+where
+    T: DivAssign<T> + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
 impl<T> Mul<T> for Pt3<T>
 where
     T: Mul<Output = T> + Copy,
@@ -179,7 +444,7 @@ where
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
-            z: self.y * rhs,
+            z: self.z * rhs,
         }
     }
 }
@@ -203,3 +468,862 @@ where
         write!(f, "<{}, {}, {}>", self.x, self.y, self.z)
     }
 }
+
+/// A point with `N` coordinates. Points are ordered by their last coordinate first,
+/// like `Pt` and `Pt3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PtN<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> PtN<T, N>
+where
+    T: Ord + Add<Output = T> + Sub<Output = T> + Zero + Copy,
+{
+    /// The Manhattan distance, or 0 for points without coordinates.
+    pub fn manhattan(&self, other: &PtN<T, N>) -> T {
+        self.diffs(other).fold(T::zero(), |sum, d| sum + d)
+    }
+
+    /// The Chebyshev distance, or 0 for points without coordinates.
+    pub fn chebyshev(&self, other: &PtN<T, N>) -> T {
+        self.diffs(other).fold(T::zero(), T::max)
+    }
+
+    pub fn euclidean(&self, other: &PtN<T, N>) -> f64
+    where
+        T: AsPrimitive<f64>,
+    {
+        self.diffs(other)
+            .map(|d| d.as_().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// The smallest and the largest corner of the box around `pts`, or `None` if there are none.
+    pub fn bounding_box(
+        pts: impl IntoIterator<Item = PtN<T, N>>,
+    ) -> Option<(PtN<T, N>, PtN<T, N>)> {
+        let mut pts = pts.into_iter();
+        let first = pts.next()?;
+        Some(pts.fold((first, first), |(min, max), pt| {
+            (
+                PtN(array::from_fn(|i| min.0[i].min(pt.0[i]))),
+                PtN(array::from_fn(|i| max.0[i].max(pt.0[i]))),
+            )
+        }))
+    }
+
+    fn diffs<'a>(&'a self, other: &'a PtN<T, N>) -> impl Iterator<Item = T> + 'a {
+        self.0.iter().zip(&other.0).map(|(&a, &b)| abs_diff(a, b))
+    }
+}
+
+impl<T, const N: usize> Default for PtN<T, N>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> PartialOrd for PtN<T, N>
+where
+    T: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, const N: usize> Ord for PtN<T, N>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PtN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PtN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for PtN<T, N> {
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<T, const N: usize> From<PtN<T, N>> for [T; N] {
+    fn from(value: PtN<T, N>) -> Self {
+        value.0
+    }
+}
+
+impl<T> From<Pt<T>> for PtN<T, 2> {
+    fn from(value: Pt<T>) -> Self {
+        Self([value.x, value.y])
+    }
+}
+
+impl<T> From<PtN<T, 2>> for Pt<T> {
+    fn from(value: PtN<T, 2>) -> Self {
+        let [x, y] = value.0;
+        Self { x, y }
+    }
+}
+
+impl<T> From<Pt3<T>> for PtN<T, 3> {
+    fn from(value: Pt3<T>) -> Self {
+        Self([value.x, value.y, value.z])
+    }
+}
+
+impl<T> From<PtN<T, 3>> for Pt3<T> {
+    fn from(value: PtN<T, 3>) -> Self {
+        let [x, y, z] = value.0;
+        Self { x, y, z }
+    }
+}
+
+impl<T, const N: usize> Add for PtN<T, N>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = PtN<T, N>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T, const N: usize> AddAssign for PtN<T, N>
+where
+    T: AddAssign<T> + Copy,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<T, const N: usize> Sub for PtN<T, N>
+where
+    T: Sub<Output = T> + Copy,
+{
+    type Output = PtN<T, N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T, const N: usize> SubAssign for PtN<T, N>
+where
+    T: SubAssign<T> + Copy,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        for (a, b) in self.0.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<T, const N: usize> Neg for PtN<T, N>
+where
+    T: Neg<Output = T>,
+{
+    type Output = PtN<T, N>;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|a| -a))
+    }
+}
+
+impl<T, const N: usize> Mul<T> for PtN<T, N>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = PtN<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self(self.0.map(|a| a * rhs))
+    }
+}
+
+impl<T, const N: usize> MulAssign<T> for PtN<T, N>
+where
+    T: MulAssign<T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        for a in &mut self.0 {
+            *a *= rhs;
+        }
+    }
+}
+
+impl<T, const N: usize> Div<T> for PtN<T, N>
+where
+    T: Div<Output = T> + Copy,
+{
+    type Output = PtN<T, N>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self(self.0.map(|a| a / rhs))
+    }
+}
+
+impl<T, const N: usize> DivAssign<T> for PtN<T, N>
+where
+    T: DivAssign<T> + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        for a in &mut self.0 {
+            *a /= rhs;
+        }
+    }
+}
+
+impl<T, const N: usize> FromStr for PtN<T, N>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coords = s
+            .trim()
+            .trim_start_matches(['(', '<'])
+            .trim_end_matches([')', '>'])
+            .split(',')
+            .map(|c| c.trim().parse())
+            .collect::<Result<Vec<T>, _>>()?;
+        let coords = coords
+            .try_into()
+            .map_err(|_| anyhow!("{s} must have {N} coordinates to be parsed as PtN"))?;
+        Ok(Self(coords))
+    }
+}
+
+impl<T, const N: usize> Display for PtN<T, N>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{c}")?;
+        }
+        write!(f, ">")
+    }
+}
+
+/// One of the four cardinal directions, on a grid whose y axis points down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Dir4 {
+    /// All directions, in reading order of the points they lead to.
+    pub const ALL: [Dir4; 4] = [Dir4::N, Dir4::W, Dir4::E, Dir4::S];
+
+    const CLOCKWISE: [Dir4; 4] = [Dir4::N, Dir4::E, Dir4::S, Dir4::W];
+
+    pub fn iter() -> impl Iterator<Item = Dir4> {
+        Self::ALL.into_iter()
+    }
+
+    fn turn(self, quarters: usize) -> Self {
+        Self::CLOCKWISE[(self as usize + quarters) % 4]
+    }
+
+    /// Turns 90° counterclockwise.
+    pub fn left(self) -> Self {
+        self.turn(3)
+    }
+
+    /// Turns 90° clockwise.
+    pub fn right(self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn(2)
+    }
+
+    pub fn offset(self) -> (isize, isize) {
+        Dir8::from(self).offset()
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'N' | 'U' | '^' => Ok(Dir4::N),
+            'E' | 'R' | '>' => Ok(Dir4::E),
+            'S' | 'D' | 'v' => Ok(Dir4::S),
+            'W' | 'L' | '<' => Ok(Dir4::W),
+            _ => Err(anyhow!("{value} is not a valid direction")),
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(anyhow!("{s} is not a valid direction")),
+        }
+    }
+}
+
+/// One of the four cardinal and four ordinal directions, on a grid whose y axis points down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions, in reading order of the points they lead to.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::NW,
+        Dir8::N,
+        Dir8::NE,
+        Dir8::W,
+        Dir8::E,
+        Dir8::SW,
+        Dir8::S,
+        Dir8::SE,
+    ];
+
+    const CLOCKWISE: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Dir8> {
+        Self::ALL.into_iter()
+    }
+
+    fn turn(self, eighths: usize) -> Self {
+        Self::CLOCKWISE[(self as usize + eighths) % 8]
+    }
+
+    /// Turns 45° counterclockwise.
+    pub fn left(self) -> Self {
+        self.turn(7)
+    }
+
+    /// Turns 45° clockwise.
+    pub fn right(self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir8::N => (0, -1),
+            Dir8::NE => (1, -1),
+            Dir8::E => (1, 0),
+            Dir8::SE => (1, 1),
+            Dir8::S => (0, 1),
+            Dir8::SW => (-1, 1),
+            Dir8::W => (-1, 0),
+            Dir8::NW => (-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(value: Dir4) -> Self {
+        match value {
+            Dir4::N => Dir8::N,
+            Dir4::E => Dir8::E,
+            Dir4::S => Dir8::S,
+            Dir4::W => Dir8::W,
+        }
+    }
+}
+
+impl TryFrom<char> for Dir8 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Dir4::try_from(value).map(Dir8::from)
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = anyhow::Error;
+
+    /// Parses a cardinal direction like `N`, or an ordinal direction made up of a vertical and
+    /// a horizontal one, like `NE`, `UR` or `^>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || anyhow!("{s} is not a valid direction");
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, _) => c.try_into(),
+            (Some(a), Some(b), None) => {
+                let dir = |c| Dir4::try_from(c).map_err(|_| err());
+                match (dir(a)?, dir(b)?) {
+                    (Dir4::N, Dir4::E) | (Dir4::E, Dir4::N) => Ok(Dir8::NE),
+                    (Dir4::S, Dir4::E) | (Dir4::E, Dir4::S) => Ok(Dir8::SE),
+                    (Dir4::S, Dir4::W) | (Dir4::W, Dir4::S) => Ok(Dir8::SW),
+                    (Dir4::N, Dir4::W) | (Dir4::W, Dir4::N) => Ok(Dir8::NW),
+                    _ => Err(err()),
+                }
+            }
+            _ => Err(err()),
+        }
+    }
+}
+
+fn step<T>(value: T, delta: isize) -> T
+where
+    T: Add<Output = T> + Sub<Output = T> + One,
+{
+    match delta {
+        1 => value + T::one(),
+        -1 => value - T::one(),
+        _ => value,
+    }
+}
+
+fn checked_step<T>(value: T, delta: isize) -> Option<T>
+where
+    T: CheckedAdd + CheckedSub + One,
+{
+    match delta {
+        1 => value.checked_add(&T::one()),
+        -1 => value.checked_sub(&T::one()),
+        _ => Some(value),
+    }
+}
+
+impl<T> Pt<T>
+where
+    T: CheckedAdd + CheckedSub + One,
+{
+    /// Steps one point into `dir`, or `None` if the coordinates would overflow,
+    /// e.g. when stepping north of `y = 0` with unsigned coordinates.
+    pub fn checked_step(self, dir: impl Into<Dir8>) -> Option<Self> {
+        let (dx, dy) = dir.into().offset();
+        Some(Self {
+            x: checked_step(self.x, dx)?,
+            y: checked_step(self.y, dy)?,
+        })
+    }
+}
+
+impl<T> Add<Dir8> for Pt<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + One,
+{
+    type Output = Pt<T>;
+
+    fn add(self, rhs: Dir8) -> Self::Output {
+        let (dx, dy) = rhs.offset();
+        Pt {
+            x: step(self.x, dx),
+            y: step(self.y, dy),
+        }
+    }
+}
+
+impl<T> Add<Dir4> for Pt<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + One,
+{
+    type Output = Pt<T>;
+
+    fn add(self, rhs: Dir4) -> Self::Output {
+        self + Dir8::from(rhs)
+    }
+}
+
+impl<T> AddAssign<Dir8> for Pt<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + One + Copy,
+{
+    fn add_assign(&mut self, rhs: Dir8) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> AddAssign<Dir4> for Pt<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + One + Copy,
+{
+    fn add_assign(&mut self, rhs: Dir4) {
+        *self = *self + rhs;
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Pt, Pt3, PtN};
+
+    /// Sorts offsets the way reading order sorts the points they lead to.
+    fn reading_order(mut offsets: Vec<(isize, isize)>) -> Vec<(isize, isize)> {
+        offsets.sort_by_key(|&(dx, dy)| (dy, dx));
+        offsets
+    }
+
+    #[test]
+    fn lists_directions_in_reading_order() {
+        let offsets: Vec<_> = Dir4::iter().map(Dir4::offset).collect();
+        assert_eq!(offsets, reading_order(offsets.clone()));
+        assert_eq!(offsets, [(0, -1), (-1, 0), (1, 0), (0, 1)]);
+
+        let offsets: Vec<_> = Dir8::iter().map(Dir8::offset).collect();
+        assert_eq!(offsets, reading_order(offsets.clone()));
+        assert_eq!(offsets.len(), 8);
+        assert!(!offsets.contains(&(0, 0)));
+    }
+
+    #[test]
+    fn turns_dir4() {
+        assert_eq!(Dir4::N.right(), Dir4::E);
+        assert_eq!(Dir4::N.left(), Dir4::W);
+        assert_eq!(Dir4::E.reverse(), Dir4::W);
+        for dir in Dir4::iter() {
+            assert_eq!(dir.left().right(), dir);
+            assert_eq!(dir.right().right(), dir.reverse());
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.right().right().right().right(), dir);
+        }
+    }
+
+    #[test]
+    fn turns_dir8() {
+        assert_eq!(Dir8::N.right(), Dir8::NE);
+        assert_eq!(Dir8::N.left(), Dir8::NW);
+        assert_eq!(Dir8::NW.right(), Dir8::N);
+        assert_eq!(Dir8::SE.reverse(), Dir8::NW);
+        for dir in Dir8::iter() {
+            assert_eq!(dir.left().right(), dir);
+            assert_eq!(dir.right().right().right().right(), dir.reverse());
+            let (dx, dy) = dir.offset();
+            assert_eq!(dir.reverse().offset(), (-dx, -dy));
+        }
+        for dir in Dir4::iter() {
+            assert_eq!(Dir8::from(dir.right()), Dir8::from(dir).right().right());
+            assert_eq!(Dir8::from(dir).offset(), dir.offset());
+        }
+    }
+
+    #[test]
+    fn parses_dir4() {
+        for (dir, chars) in [
+            (Dir4::N, "NU^"),
+            (Dir4::E, "ER>"),
+            (Dir4::S, "SDv"),
+            (Dir4::W, "WL<"),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Dir4::try_from(c).unwrap(), dir);
+                assert_eq!(c.to_string().parse::<Dir4>().unwrap(), dir);
+            }
+        }
+        assert_eq!(" S ".parse::<Dir4>().unwrap(), Dir4::S);
+        assert_eq!(
+            Dir4::try_from('x').unwrap_err().to_string(),
+            "x is not a valid direction"
+        );
+        assert!("NE".parse::<Dir4>().is_err());
+        assert!("".parse::<Dir4>().is_err());
+    }
+
+    #[test]
+    fn parses_dir8() {
+        assert_eq!("N".parse::<Dir8>().unwrap(), Dir8::N);
+        assert_eq!("NE".parse::<Dir8>().unwrap(), Dir8::NE);
+        assert_eq!("EN".parse::<Dir8>().unwrap(), Dir8::NE);
+        assert_eq!("DL".parse::<Dir8>().unwrap(), Dir8::SW);
+        assert_eq!("^<".parse::<Dir8>().unwrap(), Dir8::NW);
+        assert_eq!("SE".parse::<Dir8>().unwrap(), Dir8::SE);
+        assert_eq!(
+            "NS".parse::<Dir8>().unwrap_err().to_string(),
+            "NS is not a valid direction"
+        );
+        assert!("Nx".parse::<Dir8>().is_err());
+        assert!("NEW".parse::<Dir8>().is_err());
+        assert!("".parse::<Dir8>().is_err());
+    }
+
+    #[test]
+    fn steps_points() {
+        let pt = Pt { x: 0usize, y: 5 };
+        assert_eq!(pt + Dir4::S, Pt { x: 0, y: 6 });
+        assert_eq!(pt + Dir8::NE, Pt { x: 1, y: 4 });
+        assert_eq!(pt.checked_step(Dir4::W), None);
+        assert_eq!(pt.checked_step(Dir8::SE), Some(Pt { x: 1, y: 6 }));
+        let mut pt = Pt { x: 3i32, y: 3 };
+        pt += Dir4::N;
+        pt += Dir8::SW;
+        assert_eq!(pt, Pt { x: 2, y: 3 });
+    }
+
+    #[test]
+    fn computes_with_pts() {
+        let a = Pt { x: 1, y: -2 };
+        let b = Pt { x: 4, y: 3 };
+        assert_eq!(a + b, Pt { x: 5, y: 1 });
+        assert_eq!(b - a, Pt { x: 3, y: 5 });
+        assert_eq!(-a, Pt { x: -1, y: 2 });
+        assert_eq!(b * 2, Pt { x: 8, y: 6 });
+        assert_eq!(b / 2, Pt { x: 2, y: 1 });
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 3;
+        c /= 2;
+        assert_eq!(c, Pt { x: 6, y: 4 });
+    }
+
+    #[test]
+    fn measures_pt_distances() {
+        let a = Pt { x: 1, y: -2 };
+        let b = Pt { x: 4, y: 3 };
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(a.chebyshev(&b), 5);
+        let (a, b) = (Pt { x: 7usize, y: 0 }, Pt { x: 2, y: 3 });
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(b.manhattan(&a), 8);
+    }
+
+    #[test]
+    fn orders_pts_in_reading_order() {
+        let mut pts = vec![
+            Pt { x: 0, y: 1 },
+            Pt { x: 2, y: 0 },
+            Pt { x: 1, y: 1 },
+            Pt { x: 0, y: 0 },
+        ];
+        pts.sort();
+        assert_eq!(
+            pts,
+            [
+                Pt { x: 0, y: 0 },
+                Pt { x: 2, y: 0 },
+                Pt { x: 0, y: 1 },
+                Pt { x: 1, y: 1 }
+            ]
+        );
+    }
+
+    #[test]
+    fn bounds_pts() {
+        let pts = [Pt { x: 3, y: -1 }, Pt { x: -2, y: 4 }, Pt { x: 0, y: 0 }];
+        assert_eq!(
+            Pt::bounding_box(pts),
+            Some((Pt { x: -2, y: -1 }, Pt { x: 3, y: 4 }))
+        );
+        assert_eq!(Pt::<i32>::bounding_box([]), None);
+    }
+
+    #[test]
+    fn parses_and_displays_pts() {
+        let pt = Pt { x: 3, y: -4 };
+        assert_eq!("3,-4".parse::<Pt<i32>>().unwrap(), pt);
+        assert_eq!("3, -4".parse::<Pt<i32>>().unwrap(), pt);
+        assert_eq!("< 3, -4>".parse::<Pt<i32>>().unwrap(), pt);
+        assert_eq!(pt.to_string(), "(3, -4)");
+        assert_eq!(pt.to_string().parse::<Pt<i32>>().unwrap(), pt);
+        assert_eq!(
+            "3 4".parse::<Pt<i32>>().unwrap_err().to_string(),
+            "3 4 must include a comma to be parsed as Pt"
+        );
+        assert!("3,x".parse::<Pt<i32>>().is_err());
+        assert!("-3,4".parse::<Pt<u32>>().is_err());
+    }
+
+    #[test]
+    fn computes_with_pt3s() {
+        let a = Pt3 { x: 1, y: 2, z: 3 };
+        let b = Pt3 { x: -1, y: 5, z: 0 };
+        assert_eq!(a + b, Pt3 { x: 0, y: 7, z: 3 });
+        assert_eq!(a - b, Pt3 { x: 2, y: -3, z: 3 });
+        assert_eq!(
+            -a,
+            Pt3 {
+                x: -1,
+                y: -2,
+                z: -3
+            }
+        );
+        // `z` used to be multiplied from `y`.
+        assert_eq!(a * 2, Pt3 { x: 2, y: 4, z: 6 });
+        assert_eq!(a * 3 / 3, a);
+        let mut c = a;
+        c *= 2;
+        c += b;
+        c -= a;
+        assert_eq!(c, Pt3 { x: 0, y: 7, z: 3 });
+        c /= 3;
+        assert_eq!(c, Pt3 { x: 0, y: 2, z: 1 });
+    }
+
+    #[test]
+    fn measures_pt3_distances() {
+        let a = Pt3 { x: 1, y: 2, z: 3 };
+        let b = Pt3 { x: -1, y: 5, z: 0 };
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(a.chebyshev(&b), 3);
+        let (a, b) = (
+            Pt3 {
+                x: 1isize,
+                y: 2,
+                z: 3,
+            },
+            Pt3 { x: -1, y: 5, z: 0 },
+        );
+        assert_eq!(a.abs_norm(&b), 8);
+    }
+
+    #[test]
+    fn orders_and_bounds_pt3s() {
+        let mut pts = vec![
+            Pt3 { x: 0, y: 0, z: 1 },
+            Pt3 { x: 0, y: 1, z: 0 },
+            Pt3 { x: 1, y: 0, z: 0 },
+        ];
+        pts.sort();
+        assert_eq!(
+            pts,
+            [
+                Pt3 { x: 1, y: 0, z: 0 },
+                Pt3 { x: 0, y: 1, z: 0 },
+                Pt3 { x: 0, y: 0, z: 1 }
+            ]
+        );
+        assert_eq!(
+            Pt3::bounding_box(pts),
+            Some((Pt3 { x: 0, y: 0, z: 0 }, Pt3 { x: 1, y: 1, z: 1 }))
+        );
+    }
+
+    #[test]
+    fn parses_and_displays_pt3s() {
+        let pt = Pt3 { x: 1, y: -2, z: 3 };
+        assert_eq!("<1,-2,3>".parse::<Pt3<i32>>().unwrap(), pt);
+        assert_eq!(" 1, -2, 3 ".parse::<Pt3<i32>>().unwrap(), pt);
+        assert_eq!(pt.to_string(), "<1, -2, 3>");
+        assert_eq!(
+            "1,2".parse::<Pt3<i32>>().unwrap_err().to_string(),
+            "1,2 must have three coordinates to be parsed as Pt3"
+        );
+        assert!("1,2,3,4".parse::<Pt3<i32>>().is_err());
+        assert!("1,2,z".parse::<Pt3<i32>>().is_err());
+    }
+
+    #[test]
+    fn computes_with_ptns() {
+        let a = PtN([1, -2, 3, 0]);
+        let b = PtN([4, 2, -1, 0]);
+        assert_eq!(a + b, PtN([5, 0, 2, 0]));
+        assert_eq!(a - b, PtN([-3, -4, 4, 0]));
+        assert_eq!(-a, PtN([-1, 2, -3, 0]));
+        assert_eq!(a * 2, PtN([2, -4, 6, 0]));
+        assert_eq!(b / 2, PtN([2, 1, 0, 0]));
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 3;
+        c /= 3;
+        c[3] = 7;
+        assert_eq!(c, PtN([4, 2, -1, 7]));
+        assert_eq!(PtN::<i32, 2>::default(), PtN([0, 0]));
+    }
+
+    #[test]
+    fn measures_ptn_distances() {
+        let a = PtN([1, -2, 3, 0]);
+        let b = PtN([4, 2, -1, 0]);
+        assert_eq!(a.manhattan(&b), 11);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(PtN([0, 0]).euclidean(&PtN([3, -4])), 5.0);
+        assert_eq!(PtN::<i32, 0>([]).manhattan(&PtN([])), 0);
+        assert_eq!(PtN::<u8, 0>([]).chebyshev(&PtN([])), 0);
+    }
+
+    #[test]
+    fn orders_and_bounds_ptns() {
+        let mut pts = vec![PtN([0, 0, 1]), PtN([0, 1, 0]), PtN([1, 0, 0])];
+        pts.sort();
+        assert_eq!(pts, [PtN([1, 0, 0]), PtN([0, 1, 0]), PtN([0, 0, 1])]);
+        let ordered: Vec<Pt3<i32>> = pts.iter().map(|&p| p.into()).collect();
+        assert!(ordered.is_sorted());
+        assert_eq!(
+            PtN::bounding_box(pts),
+            Some((PtN([0, 0, 0]), PtN([1, 1, 1])))
+        );
+        assert_eq!(PtN::<i32, 3>::bounding_box([]), None);
+    }
+
+    #[test]
+    fn converts_ptns() {
+        let pt = Pt { x: 1, y: 2 };
+        assert_eq!(PtN::from(pt), PtN([1, 2]));
+        assert_eq!(Pt::from(PtN([1, 2])), pt);
+        let pt3 = Pt3 { x: 1, y: 2, z: 3 };
+        assert_eq!(PtN::from(pt3), PtN([1, 2, 3]));
+        assert_eq!(Pt3::from(PtN([1, 2, 3])), pt3);
+        assert_eq!(<[i32; 2]>::from(PtN([1, 2])), [1, 2]);
+    }
+
+    #[test]
+    fn parses_and_displays_ptns() {
+        let pt = PtN([1, -2, 3, 0]);
+        assert_eq!("1,-2,3,0".parse::<PtN<i32, 4>>().unwrap(), pt);
+        assert_eq!(" <1, -2, 3, 0> ".parse::<PtN<i32, 4>>().unwrap(), pt);
+        assert_eq!(pt.to_string(), "<1, -2, 3, 0>");
+        assert_eq!(pt.to_string().parse::<PtN<i32, 4>>().unwrap(), pt);
+        assert_eq!(
+            "1,2,3".parse::<PtN<i32, 4>>().unwrap_err().to_string(),
+            "1,2,3 must have 4 coordinates to be parsed as PtN"
+        );
+        assert!("1,2,3,4,5".parse::<PtN<i32, 4>>().is_err());
+        assert!("1,2,x,4".parse::<PtN<i32, 4>>().is_err());
+    }
+}